@@ -45,6 +45,7 @@ CREATE TABLE `jobs` (
   `job_apply_is_direct` tinyint(1) DEFAULT NULL,
   `job_apply_quality_score` decimal(6,2) DEFAULT NULL,
   `job_description` longtext DEFAULT NULL,
+  `job_description_raw` longtext DEFAULT NULL,
   `job_is_remote` tinyint(1) DEFAULT NULL,
   `job_posted_human_readable` varchar(64) DEFAULT NULL,
   `job_posted_at_timestamp` bigint(20) DEFAULT NULL,
@@ -78,7 +79,18 @@ CREATE TABLE `jobs` (
   `updated_at` timestamp NOT NULL DEFAULT current_timestamp() ON UPDATE current_timestamp(),
   `matching_analysis` text NOT NULL,
   `cover_letter` text DEFAULT NULL,
-  `isdelete` tinyint(1) NOT NULL DEFAULT 0
+  `isdelete` tinyint(1) NOT NULL DEFAULT 0,
+  `upstream_missing_at` datetime DEFAULT NULL,
+  `last_seen_at` datetime DEFAULT NULL,
+  `expired_at` datetime DEFAULT NULL,
+  `expired_reason` varchar(16) DEFAULT NULL,
+  `dedup_fingerprint` char(32) DEFAULT NULL,
+  `canonical_job_id` varchar(64) DEFAULT NULL,
+  `salary_annual_min` decimal(18,2) DEFAULT NULL,
+  `salary_annual_max` decimal(18,2) DEFAULT NULL,
+  `salary_annual_currency` char(3) DEFAULT NULL,
+  `workplace_type` varchar(8) DEFAULT NULL,
+  `remote_region_restriction` varchar(100) DEFAULT NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
 
 --
@@ -94,13 +106,158 @@ CREATE TABLE `job_apply_options` (
   `publisher` varchar(255) DEFAULT NULL,
   `apply_link` varchar(1024) DEFAULT NULL,
   `is_direct` tinyint(1) DEFAULT NULL,
-  `created_at` timestamp NOT NULL DEFAULT current_timestamp()
+  `created_at` timestamp NOT NULL DEFAULT current_timestamp(),
+  `link_status` smallint(5) UNSIGNED DEFAULT NULL,
+  `link_final_url` varchar(1024) DEFAULT NULL,
+  `link_alive` tinyint(1) DEFAULT NULL,
+  `link_error` varchar(255) DEFAULT NULL,
+  `link_checked_at` datetime DEFAULT NULL,
+  `link_fail_count` smallint(5) UNSIGNED NOT NULL DEFAULT 0,
+  `copied_from_job_id` varchar(64) DEFAULT NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
 
 --
 -- Dumping data for table `job_apply_options`
 --
 
+--
+-- Table structure for table `api_calls`
+--
+
+CREATE TABLE `api_calls` (
+  `id` bigint(20) UNSIGNED NOT NULL,
+  `host` varchar(255) NOT NULL,
+  `endpoint` varchar(255) NOT NULL,
+  `status_code` smallint(5) UNSIGNED DEFAULT NULL,
+  `latency_ms` bigint(20) NOT NULL DEFAULT 0,
+  `requests_remaining` bigint(20) DEFAULT NULL,
+  `ratelimit_json` longtext CHARACTER SET utf8mb4 COLLATE utf8mb4_bin DEFAULT NULL CHECK (json_valid(`ratelimit_json`)),
+  `error` varchar(512) DEFAULT NULL,
+  `created_at` timestamp NOT NULL DEFAULT current_timestamp()
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `saved_searches`
+--
+
+CREATE TABLE `saved_searches` (
+  `id` bigint(20) UNSIGNED NOT NULL,
+  `source` varchar(32) NOT NULL,
+  `query` varchar(255) NOT NULL,
+  `country` varchar(16) DEFAULT NULL,
+  `language` varchar(16) DEFAULT NULL,
+  `date_posted` varchar(16) DEFAULT NULL,
+  `num_pages` int(11) NOT NULL DEFAULT 1,
+  `location` varchar(255) DEFAULT NULL,
+  `experience_levels` varchar(255) DEFAULT NULL,
+  `workplace_types` varchar(255) DEFAULT NULL,
+  `employment_types` varchar(255) DEFAULT NULL,
+  `interval_minutes` int(11) NOT NULL DEFAULT 360,
+  `is_active` tinyint(1) NOT NULL DEFAULT 1,
+  `last_run_at` datetime DEFAULT NULL,
+  `created_at` timestamp NOT NULL DEFAULT current_timestamp()
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `search_cursors`
+--
+
+CREATE TABLE `search_cursors` (
+  `source` varchar(32) NOT NULL,
+  `query_key` varchar(512) NOT NULL,
+  `next_cursor` text DEFAULT NULL,
+  `updated_at` datetime NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `salary_estimates`
+--
+
+CREATE TABLE `salary_estimates` (
+  `title_key` varchar(255) NOT NULL,
+  `location_key` varchar(255) NOT NULL,
+  `job_title` varchar(512) NOT NULL,
+  `location` varchar(255) NOT NULL,
+  `min_salary` decimal(18,2) DEFAULT NULL,
+  `max_salary` decimal(18,2) DEFAULT NULL,
+  `median_salary` decimal(18,2) DEFAULT NULL,
+  `salary_currency` varchar(8) DEFAULT NULL,
+  `salary_period` varchar(32) DEFAULT NULL,
+  `publishers` varchar(512) DEFAULT NULL,
+  `estimates_json` longtext CHARACTER SET utf8mb4 COLLATE utf8mb4_bin DEFAULT NULL CHECK (json_valid(`estimates_json`)),
+  `fetched_at` datetime NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `search_cache`
+--
+
+CREATE TABLE `search_cache` (
+  `cache_key` char(64) NOT NULL,
+  `source` varchar(32) NOT NULL,
+  `params_json` longtext CHARACTER SET utf8mb4 COLLATE utf8mb4_bin DEFAULT NULL CHECK (json_valid(`params_json`)),
+  `response_json` longtext CHARACTER SET utf8mb4 COLLATE utf8mb4_bin NOT NULL CHECK (json_valid(`response_json`)),
+  `result_count` int(11) NOT NULL DEFAULT 0,
+  `fetched_at` datetime NOT NULL,
+  `expires_at` datetime NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `search_runs`
+--
+
+CREATE TABLE `search_runs` (
+  `id` bigint(20) UNSIGNED NOT NULL,
+  `source` varchar(32) NOT NULL,
+  `origin` varchar(16) NOT NULL DEFAULT 'manual',
+  `saved_search_id` bigint(20) UNSIGNED DEFAULT NULL,
+  `query` varchar(255) NOT NULL DEFAULT '',
+  `params_json` longtext CHARACTER SET utf8mb4 COLLATE utf8mb4_bin DEFAULT NULL CHECK (json_valid(`params_json`)),
+  `started_at` datetime NOT NULL,
+  `finished_at` datetime NOT NULL,
+  `pages` int(11) NOT NULL DEFAULT 0,
+  `result_count` int(11) NOT NULL DEFAULT 0,
+  `new_count` int(11) NOT NULL DEFAULT 0,
+  `updated_count` int(11) NOT NULL DEFAULT 0,
+  `cached_pages` int(11) NOT NULL DEFAULT 0,
+  `error` text DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `search_run_jobs`
+--
+
+CREATE TABLE `search_run_jobs` (
+  `run_id` bigint(20) UNSIGNED NOT NULL,
+  `job_id` varchar(64) NOT NULL,
+  `is_new` tinyint(1) NOT NULL DEFAULT 0
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- --------------------------------------------------------
+
+--
+-- Table structure for table `job_skills`
+--
+
+CREATE TABLE `job_skills` (
+  `job_id` varchar(64) NOT NULL,
+  `skill` varchar(100) NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- --------------------------------------------------------
+
 --
 -- Table structure for table `myresume`
 --
@@ -128,14 +285,76 @@ ALTER TABLE `jobs`
   ADD KEY `idx_title` (`job_title`(191)),
   ADD KEY `idx_employer` (`employer_name`(191)),
   ADD KEY `idx_city` (`job_city`),
-  ADD KEY `idx_country` (`job_country`);
+  ADD KEY `idx_country` (`job_country`),
+  ADD KEY `idx_updated_at` (`updated_at`),
+  ADD KEY `idx_dedup_fingerprint` (`dedup_fingerprint`),
+  ADD KEY `idx_canonical_job_id` (`canonical_job_id`),
+  ADD KEY `idx_expired_at` (`expired_at`),
+  ADD KEY `idx_salary_annual` (`salary_annual_max`,`salary_annual_min`),
+  ADD KEY `idx_workplace` (`workplace_type`,`remote_region_restriction`);
 
 --
 -- Indexes for table `job_apply_options`
 --
 ALTER TABLE `job_apply_options`
   ADD PRIMARY KEY (`id`),
-  ADD UNIQUE KEY `uniq_job_link` (`job_id`,`apply_link`(191));
+  ADD UNIQUE KEY `uniq_job_link` (`job_id`,`apply_link`(191)),
+  ADD KEY `idx_link_checked_at` (`link_checked_at`),
+  ADD KEY `idx_copied_from_job_id` (`copied_from_job_id`);
+
+--
+-- Indexes for table `api_calls`
+--
+ALTER TABLE `api_calls`
+  ADD PRIMARY KEY (`id`),
+  ADD KEY `idx_host_created` (`host`,`created_at`);
+
+--
+-- Indexes for table `saved_searches`
+--
+ALTER TABLE `saved_searches`
+  ADD PRIMARY KEY (`id`);
+
+--
+-- Indexes for table `search_runs`
+--
+ALTER TABLE `search_runs`
+  ADD PRIMARY KEY (`id`),
+  ADD KEY `idx_source_started` (`source`,`started_at`),
+  ADD KEY `idx_saved_search` (`saved_search_id`);
+
+--
+-- Indexes for table `search_run_jobs`
+--
+ALTER TABLE `search_run_jobs`
+  ADD PRIMARY KEY (`run_id`,`job_id`),
+  ADD KEY `idx_job_id` (`job_id`);
+
+--
+-- Indexes for table `job_skills`
+--
+ALTER TABLE `job_skills`
+  ADD PRIMARY KEY (`job_id`,`skill`),
+  ADD KEY `idx_skill` (`skill`);
+
+--
+-- Indexes for table `search_cursors`
+--
+ALTER TABLE `search_cursors`
+  ADD PRIMARY KEY (`source`,`query_key`);
+
+--
+-- Indexes for table `salary_estimates`
+--
+ALTER TABLE `salary_estimates`
+  ADD PRIMARY KEY (`title_key`,`location_key`);
+
+--
+-- Indexes for table `search_cache`
+--
+ALTER TABLE `search_cache`
+  ADD PRIMARY KEY (`cache_key`),
+  ADD KEY `idx_expires_at` (`expires_at`);
 
 --
 -- Indexes for table `myresume`
@@ -153,6 +372,24 @@ ALTER TABLE `myresume`
 ALTER TABLE `job_apply_options`
   MODIFY `id` bigint(20) UNSIGNED NOT NULL AUTO_INCREMENT, AUTO_INCREMENT=794;
 
+--
+-- AUTO_INCREMENT for table `api_calls`
+--
+ALTER TABLE `api_calls`
+  MODIFY `id` bigint(20) UNSIGNED NOT NULL AUTO_INCREMENT;
+
+--
+-- AUTO_INCREMENT for table `saved_searches`
+--
+ALTER TABLE `saved_searches`
+  MODIFY `id` bigint(20) UNSIGNED NOT NULL AUTO_INCREMENT;
+
+--
+-- AUTO_INCREMENT for table `search_runs`
+--
+ALTER TABLE `search_runs`
+  MODIFY `id` bigint(20) UNSIGNED NOT NULL AUTO_INCREMENT;
+
 --
 -- AUTO_INCREMENT for table `myresume`
 --
@@ -168,6 +405,25 @@ ALTER TABLE `myresume`
 --
 ALTER TABLE `job_apply_options`
   ADD CONSTRAINT `fk_apply_job` FOREIGN KEY (`job_id`) REFERENCES `jobs` (`job_id`) ON DELETE CASCADE;
+
+--
+-- Constraints for table `search_runs`
+--
+ALTER TABLE `search_runs`
+  ADD CONSTRAINT `fk_search_run_saved_search` FOREIGN KEY (`saved_search_id`) REFERENCES `saved_searches` (`id`) ON DELETE SET NULL;
+
+--
+-- Constraints for table `search_run_jobs`
+--
+ALTER TABLE `search_run_jobs`
+  ADD CONSTRAINT `fk_search_run_job_run` FOREIGN KEY (`run_id`) REFERENCES `search_runs` (`id`) ON DELETE CASCADE,
+  ADD CONSTRAINT `fk_search_run_job_job` FOREIGN KEY (`job_id`) REFERENCES `jobs` (`job_id`) ON DELETE CASCADE;
+
+--
+-- Constraints for table `job_skills`
+--
+ALTER TABLE `job_skills`
+  ADD CONSTRAINT `fk_job_skill_job` FOREIGN KEY (`job_id`) REFERENCES `jobs` (`job_id`) ON DELETE CASCADE;
 COMMIT;
 
 /*!40101 SET CHARACTER_SET_CLIENT=@OLD_CHARACTER_SET_CLIENT */;
@@ -6,6 +6,11 @@ use reqwest::{
 };
 use serde::{de::DeserializeOwned, Deserialize};
 
+use crate::cassette::Cassette;
+use crate::job_source::{Cursor, JobSource, SearchPage, SearchRequest};
+use crate::retry::{Retrier, RetryPolicy};
+use crate::usage::UsageTracker;
+
 fn extract_first_json_slice<'a>(raw: &'a str) -> Option<&'a str> {
     let s = raw.trim_start_matches('\u{FEFF}').trim_start();
     let start = s.find(|c: char| c == '{' || c == '[')?;
@@ -55,15 +60,48 @@ fn parse_lenient<T: DeserializeOwned>(raw: &str) -> Result<T, serde_json::Error>
 
 #[derive(Debug, Deserialize)]
 pub struct ApiResponse {
+    #[serde(default)]
+    pub request_id: Option<String>,
     #[serde(default)]
     pub data: Vec<crate::models::Job>,
 }
 
+/// Satu baris hasil `/estimated-salary` (per publisher).
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+pub struct EstimatedSalary {
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub job_title: Option<String>,
+    #[serde(default)]
+    pub publisher_name: Option<String>,
+    #[serde(default)]
+    pub min_salary: Option<f64>,
+    #[serde(default)]
+    pub max_salary: Option<f64>,
+    #[serde(default)]
+    pub median_salary: Option<f64>,
+    #[serde(default)]
+    pub salary_period: Option<String>,
+    #[serde(default)]
+    pub salary_currency: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EstimatedSalaryResponse {
+    #[serde(default)]
+    data: Vec<EstimatedSalary>,
+}
+
 #[derive(Clone)]
 pub struct JobApiClient {
     client: Client,
     api_key: String,
     host: String,
+    /// default `https://{host}`; bisa diarahkan ke server lain (`JSEARCH_BASE_URL`, mock)
+    base_url: String,
+    retry: Retrier,
+    cassette: Cassette,
 }
 
 impl JobApiClient {
@@ -74,10 +112,35 @@ impl JobApiClient {
                 .build()
                 .expect("build reqwest client"),
             api_key,
+            base_url: format!("https://{host}"),
             host,
+            retry: Retrier::new(RetryPolicy::default()),
+            cassette: Cassette::default(),
         }
     }
 
+    /// Rekam / putar ulang response lewat cassette (`HTTP_CASSETTE`).
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Retrier::new(policy);
+        self
+    }
+
+    /// Catat semua call keluar ke api_calls (panggil setelah `with_retry`).
+    pub fn with_usage(mut self, usage: UsageTracker) -> Self {
+        self.retry = self.retry.with_usage(usage, &self.host);
+        self
+    }
+
     /// (&str, i32, i32, &str, &str, &str)
     pub async fn search(
         &self,
@@ -88,17 +151,12 @@ impl JobApiClient {
         country: &str,
         language: &str,
     ) -> Result<ApiResponse, Box<dyn std::error::Error + Send + Sync>> {
-        let url = format!("https://{}/search", self.host);
+        let url = format!("{}/search", self.base_url);
 
-        let mut headers = HeaderMap::new();
-        headers.insert("X-RapidAPI-Key", HeaderValue::from_str(&self.api_key)?);
-        headers.insert("X-RapidAPI-Host", HeaderValue::from_str(&self.host)?);
-        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
-
-        let resp = self
+        let req = self
             .client
             .get(&url)
-            .headers(headers)
+            .headers(self.headers()?)
             .query(&[
                 ("query", query),
                 ("page", &page.to_string()),
@@ -106,35 +164,146 @@ impl JobApiClient {
                 ("date_posted", date_posted),
                 ("country", country),
                 ("language", language),
-            ])
-            .send()
+            ]);
+        let exchange = self
+            .cassette
+            .send("jsearch", req, Some(&self.retry))
             .await?
-            .error_for_status()?; // 4xx/5xx -> error duluan
+            .error_for_status()?; // 4xx/5xx (setelah retry habis) -> error duluan
 
-        // Ambil body mentah
-        let body: String = resp.text().await?;
+        // Decode body mentah
+        decode_body(&exchange.body).map_err(|e| format!("{e}{}", exchange.cassette_hint()).into())
+    }
 
-        // 1) Coba ambil JSON pertama (kalau ada trailing sampah)
-        if let Some(first) = extract_first_json_slice(&body) {
-            if let Ok(ok) = serde_json::from_str::<ApiResponse>(first) {
-                return Ok(ok);
-            }
+    /// Detail satu job (`/job-details`). `Ok(None)` jika job sudah tidak ada di JSearch
+    /// (404 atau `data` kosong).
+    pub async fn job_details(
+        &self,
+        job_id: &str,
+    ) -> Result<Option<crate::models::Job>, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/job-details", self.base_url);
+
+        let req = self
+            .client
+            .get(&url)
+            .headers(self.headers()?)
+            .query(&[("job_id", job_id), ("extended_publisher_details", "false")]);
+        let exchange = self.cassette.send("jsearch", req, Some(&self.retry)).await?;
+        if exchange.status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
         }
+        let exchange = exchange.error_for_status()?;
 
-        // 2) Fallback parser lenient
-        if let Ok(ok) = parse_lenient::<ApiResponse>(&body) {
+        let parsed: ApiResponse = decode_body(&exchange.body)
+            .map_err(|e| format!("{e}{}", exchange.cassette_hint()))?;
+        Ok(parsed.data.into_iter().find(|j| j.job_id == job_id).map(|mut job| {
+            if job.request_id.is_none() {
+                job.request_id = parsed.request_id.clone();
+            }
+            job
+        }))
+    }
+
+    /// Estimasi gaji untuk judul + lokasi (`/estimated-salary`), satu baris per publisher.
+    pub async fn estimated_salary(
+        &self,
+        job_title: &str,
+        location: &str,
+    ) -> Result<Vec<EstimatedSalary>, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/estimated-salary", self.base_url);
+
+        let req = self
+            .client
+            .get(&url)
+            .headers(self.headers()?)
+            .query(&[
+                ("job_title", job_title),
+                ("location", location),
+                ("location_type", "ANY"),
+                ("years_of_experience", "ALL"),
+            ]);
+        let exchange = self.cassette.send("jsearch", req, Some(&self.retry)).await?.error_for_status()?;
+
+        let parsed: EstimatedSalaryResponse = decode_body(&exchange.body)
+            .map_err(|e| format!("{e}{}", exchange.cassette_hint()))?;
+        Ok(parsed.data)
+    }
+
+    fn headers(&self) -> Result<HeaderMap, reqwest::header::InvalidHeaderValue> {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RapidAPI-Key", HeaderValue::from_str(&self.api_key)?);
+        headers.insert("X-RapidAPI-Host", HeaderValue::from_str(&self.host)?);
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        Ok(headers)
+    }
+}
+
+/// Decode body JSearch secara toleran (trailing data, BOM).
+fn decode_body<T: DeserializeOwned>(body: &str) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+    // 1) Coba ambil JSON pertama (kalau ada trailing sampah)
+    if let Some(first) = extract_first_json_slice(body) {
+        if let Ok(ok) = serde_json::from_str::<T>(first) {
             return Ok(ok);
         }
+    }
 
-        // 3) Masih gagal -> kirim error + preview
-        let mut preview = body.clone();
-        if preview.len() > 1200 {
-            preview.truncate(1200);
-            preview.push('…');
-        }
-        Err(format!(
-            "failed to decode RapidAPI response (possibly trailing data). Preview:\n{preview}"
+    // 2) Fallback parser lenient
+    if let Ok(ok) = parse_lenient::<T>(body) {
+        return Ok(ok);
+    }
+
+    // 3) Masih gagal -> kirim error + preview
+    let mut preview = body.to_string();
+    if preview.len() > 1200 {
+        preview.truncate(1200);
+        preview.push('…');
+    }
+    Err(format!(
+        "failed to decode RapidAPI response (possibly trailing data). Preview:\n{preview}"
+    )
+    .into())
+}
+
+#[async_trait::async_trait]
+impl JobSource for JobApiClient {
+    fn name(&self) -> &'static str {
+        "jsearch"
+    }
+
+    fn host(&self) -> &str {
+        &self.host
+    }
+
+    async fn search(&self, req: &SearchRequest) -> anyhow::Result<SearchPage> {
+        let page = match &req.cursor {
+            Cursor::Page(p) => (*p).max(1),
+            _ => 1,
+        };
+        let num_pages = req.num_pages.max(1);
+
+        let resp = JobApiClient::search(
+            self,
+            &req.query,
+            page,
+            num_pages,
+            req.date_posted.as_deref().unwrap_or("all"),
+            req.country.as_deref().unwrap_or("ID"),
+            req.language.as_deref().unwrap_or("en"),
         )
-        .into())
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        let mut jobs = resp.data;
+        for job in &mut jobs {
+            if job.request_id.is_none() {
+                job.request_id = resp.request_id.clone();
+            }
+            if job.search_query.is_none() {
+                job.search_query = Some(req.query.clone());
+            }
+        }
+
+        let next = if jobs.is_empty() { None } else { Some(Cursor::Page(page + num_pages)) };
+        Ok(SearchPage { jobs, next })
     }
 }
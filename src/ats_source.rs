@@ -0,0 +1,485 @@
+// src/ats_source.rs
+//
+// Sumber lowongan dari job board publik ATS: Greenhouse, Lever, Ashby.
+// Ketiganya punya JSON API publik per company slug (tanpa API key). Query pada
+// `SearchRequest` = slug perusahaan (boleh beberapa, dipisah koma); kosong = semua
+// slug yang dikonfigurasi di env. Base URL bisa diganti (mis. server lokal untuk uji).
+
+use std::env;
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+
+use crate::job_source::{JobSource, SearchPage, SearchRequest};
+use crate::linkedin_client::{employment_type_code, first_str};
+use crate::models::{ApplyOption, Job};
+use crate::retry::{Retrier, RetryPolicy};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtsKind {
+    Greenhouse,
+    Lever,
+    Ashby,
+}
+
+impl AtsKind {
+    pub const ALL: [AtsKind; 3] = [AtsKind::Greenhouse, AtsKind::Lever, AtsKind::Ashby];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AtsKind::Greenhouse => "greenhouse",
+            AtsKind::Lever => "lever",
+            AtsKind::Ashby => "ashby",
+        }
+    }
+
+    /// Prefix job_id per board.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            AtsKind::Greenhouse => "gh_",
+            AtsKind::Lever => "lever_",
+            AtsKind::Ashby => "ashby_",
+        }
+    }
+
+    fn publisher(&self) -> &'static str {
+        match self {
+            AtsKind::Greenhouse => "Greenhouse",
+            AtsKind::Lever => "Lever",
+            AtsKind::Ashby => "Ashby",
+        }
+    }
+
+    fn default_base_url(&self) -> &'static str {
+        match self {
+            AtsKind::Greenhouse => "https://boards-api.greenhouse.io",
+            AtsKind::Lever => "https://api.lever.co",
+            AtsKind::Ashby => "https://api.ashbyhq.com",
+        }
+    }
+
+    /// Prefix env: GREENHOUSE_*, LEVER_*, ASHBY_*.
+    fn env_prefix(&self) -> &'static str {
+        match self {
+            AtsKind::Greenhouse => "GREENHOUSE",
+            AtsKind::Lever => "LEVER",
+            AtsKind::Ashby => "ASHBY",
+        }
+    }
+}
+
+/// Perusahaan yang dipantau: `slug` + nama tampilan opsional (`Nama=slug` di env).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtsCompany {
+    pub slug: String,
+    pub name: Option<String>,
+}
+
+impl AtsCompany {
+    pub fn parse_list(s: &str) -> Vec<AtsCompany> {
+        s.split([',', '\n'])
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| match item.split_once('=') {
+                Some((name, slug)) => AtsCompany {
+                    slug: slug.trim().to_string(),
+                    name: Some(name.trim().to_string()).filter(|n| !n.is_empty()),
+                },
+                None => AtsCompany { slug: item.to_string(), name: None },
+            })
+            .filter(|c| !c.slug.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+pub struct AtsSource {
+    kind: AtsKind,
+    client: Client,
+    base_url: String,
+    companies: Vec<AtsCompany>,
+    retry: Retrier,
+}
+
+impl AtsSource {
+    pub fn new(kind: AtsKind) -> Self {
+        Self {
+            kind,
+            client: Client::builder()
+                .user_agent("job-finder/0.1")
+                .build()
+                .expect("build reqwest client"),
+            base_url: kind.default_base_url().to_string(),
+            companies: Vec::new(),
+            retry: Retrier::new(RetryPolicy::default()),
+        }
+    }
+
+    /// `<PREFIX>_BASE_URL` (opsional) dan `<PREFIX>_BOARDS` (daftar slug, `Nama=slug`).
+    pub fn from_env(kind: AtsKind) -> Self {
+        let prefix = kind.env_prefix();
+        let mut source = Self::new(kind);
+        if let Ok(url) = env::var(format!("{prefix}_BASE_URL")) {
+            if !url.trim().is_empty() {
+                source = source.with_base_url(url.trim());
+            }
+        }
+        source.companies = AtsCompany::parse_list(&env::var(format!("{prefix}_BOARDS")).unwrap_or_default());
+        source
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Retrier::new(policy);
+        self
+    }
+
+    fn board_url(&self, slug: &str) -> String {
+        let slug = urlencoding::encode(slug);
+        match self.kind {
+            AtsKind::Greenhouse => format!("{}/v1/boards/{slug}/jobs?content=true", self.base_url),
+            AtsKind::Lever => format!("{}/v0/postings/{slug}?mode=json", self.base_url),
+            AtsKind::Ashby => format!(
+                "{}/posting-api/job-board/{slug}?includeCompensation=true",
+                self.base_url
+            ),
+        }
+    }
+
+    /// Ambil semua posting satu perusahaan.
+    pub async fn fetch_board(&self, company: &AtsCompany) -> Result<Vec<Job>> {
+        let url = self.board_url(&company.slug);
+        let resp = self
+            .retry
+            .send(self.client.get(&url).header("Accept", "application/json"))
+            .await
+            .with_context(|| format!("GET {url}"))?;
+        if resp.status() == StatusCode::NOT_FOUND {
+            bail!("{} board '{}' tidak ditemukan", self.kind.name(), company.slug);
+        }
+        let body: Value = resp.error_for_status()?.json().await.with_context(|| format!("decode {url}"))?;
+
+        let employer = company.name.clone().unwrap_or_else(|| company.slug.clone());
+        let postings = match self.kind {
+            // Lever: array di root
+            AtsKind::Lever => body.as_array().cloned().unwrap_or_default(),
+            AtsKind::Greenhouse | AtsKind::Ashby => {
+                body.get("jobs").and_then(|j| j.as_array()).cloned().unwrap_or_default()
+            }
+        };
+
+        Ok(postings
+            .iter()
+            .filter_map(|p| match self.kind {
+                AtsKind::Greenhouse => job_from_greenhouse(p, &employer),
+                AtsKind::Lever => job_from_lever(p, &employer),
+                AtsKind::Ashby => job_from_ashby(p, &employer),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl JobSource for AtsSource {
+    fn name(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    fn host(&self) -> &str {
+        self.base_url
+            .split("://")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .unwrap_or(&self.base_url)
+    }
+
+    /// `req.query` = slug perusahaan (dipisah koma); kosong = semua slug dari env.
+    /// Board yang gagal dilewati; error hanya jika semua gagal.
+    async fn search(&self, req: &SearchRequest) -> anyhow::Result<SearchPage> {
+        let requested = AtsCompany::parse_list(&req.query);
+        let companies: Vec<AtsCompany> = if requested.is_empty() {
+            self.companies.clone()
+        } else {
+            // pakai nama tampilan dari env jika slug-nya dikenal
+            requested
+                .into_iter()
+                .map(|c| {
+                    self.companies
+                        .iter()
+                        .find(|known| known.slug.eq_ignore_ascii_case(&c.slug))
+                        .cloned()
+                        .unwrap_or(c)
+                })
+                .collect()
+        };
+        if companies.is_empty() {
+            bail!("Isi slug perusahaan atau set {}_BOARDS", self.kind.env_prefix());
+        }
+
+        let mut jobs = Vec::new();
+        let mut errors = Vec::new();
+        for company in &companies {
+            match self.fetch_board(company).await {
+                Ok(found) => jobs.extend(found),
+                Err(e) => {
+                    eprintln!("{} {} error: {e:#}", self.kind.name(), company.slug);
+                    errors.push(format!("{}: {e}", company.slug));
+                }
+            }
+        }
+        if errors.len() == companies.len() {
+            bail!("{}", errors.join("; "));
+        }
+
+        let query = req.query.trim();
+        for job in &mut jobs {
+            job.search_query = Some(if query.is_empty() { self.kind.name().to_string() } else { query.to_string() });
+        }
+        Ok(SearchPage { jobs, next: None })
+    }
+}
+
+// ==================== Mapper per board ====================
+
+/// Greenhouse mengirim `content` sebagai HTML yang di-escape.
+fn unescape_html(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn is_remote_text(s: &str) -> bool {
+    s.to_lowercase().contains("remote")
+}
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s).ok().map(|d| d.with_timezone(&Utc))
+}
+
+fn ats_job(
+    kind: AtsKind,
+    id: String,
+    employer: &str,
+    apply_link: Option<String>,
+    posting: &Value,
+) -> Job {
+    let job_id = format!("{}{id}", kind.prefix());
+    let apply_options = apply_link
+        .iter()
+        .map(|link| ApplyOption {
+            job_id: job_id.clone(),
+            publisher: Some(kind.publisher().to_string()),
+            apply_link: Some(link.clone()),
+            is_direct: Some(true),
+            ..Default::default()
+        })
+        .collect();
+
+    Job {
+        job_id,
+        employer_name: Some(employer.to_string()),
+        job_publisher: Some(kind.publisher().to_string()),
+        job_apply_is_direct: Some(apply_link.is_some()),
+        job_apply_link: apply_link,
+        raw_json: Some(posting.clone()),
+        apply_options,
+        ..Default::default()
+    }
+}
+
+pub fn job_from_greenhouse(p: &Value, employer: &str) -> Option<Job> {
+    let id = first_str(p, &["id"])?;
+    let location = first_str(p, &["location.name"]);
+    let posted = first_str(p, &["first_published", "updated_at"]).and_then(|s| parse_time(&s));
+
+    let mut job = ats_job(AtsKind::Greenhouse, id, employer, first_str(p, &["absolute_url"]), p);
+    job.job_title = first_str(p, &["title"]);
+    job.job_is_remote = location.as_deref().map(is_remote_text);
+    job.job_location = location;
+    job.job_description = first_str(p, &["content"]).map(|c| unescape_html(&c));
+    job.job_posted_at_timestamp = posted.map(|d| d.timestamp());
+    job.job_posted_at_datetime_utc = posted;
+    Some(job)
+}
+
+pub fn job_from_lever(p: &Value, employer: &str) -> Option<Job> {
+    let id = first_str(p, &["id"])?;
+    let location = first_str(p, &["categories.location"]);
+    let workplace = first_str(p, &["workplaceType"]);
+    let posted = p
+        .get("createdAt")
+        .and_then(|v| v.as_i64())
+        .and_then(DateTime::from_timestamp_millis);
+
+    // deskripsi Lever terpecah: description + lists (judul + <li>) + additional
+    let mut description = first_str(p, &["description"]).unwrap_or_default();
+    for list in p.get("lists").and_then(|l| l.as_array()).into_iter().flatten() {
+        let heading = first_str(list, &["text"]).unwrap_or_default();
+        let content = first_str(list, &["content"]).unwrap_or_default();
+        description.push_str(&format!("<h3>{heading}</h3><ul>{content}</ul>"));
+    }
+    if let Some(extra) = first_str(p, &["additional"]) {
+        description.push_str(&extra);
+    }
+
+    let apply = first_str(p, &["applyUrl", "hostedUrl"]);
+    let mut job = ats_job(AtsKind::Lever, id, employer, apply, p);
+    job.job_title = first_str(p, &["text"]);
+    job.job_is_remote = match workplace.as_deref() {
+        Some(w) if !w.eq_ignore_ascii_case("unspecified") => Some(w.eq_ignore_ascii_case("remote")),
+        _ => location.as_deref().map(is_remote_text),
+    };
+    job.job_location = location;
+    job.job_description = Some(description).filter(|d| !d.trim().is_empty());
+    if let Some(commitment) = first_str(p, &["categories.commitment"]) {
+        job.job_employment_type = Some(employment_type_code(&commitment));
+        job.job_employment_type_text = Some(commitment);
+    }
+    job.job_posted_at_timestamp = posted.map(|d| d.timestamp());
+    job.job_posted_at_datetime_utc = posted;
+    Some(job)
+}
+
+pub fn job_from_ashby(p: &Value, employer: &str) -> Option<Job> {
+    let id = first_str(p, &["id"])?;
+    let location = first_str(p, &["location"]);
+    let posted = first_str(p, &["publishedAt"]).and_then(|s| parse_time(&s));
+
+    let apply = first_str(p, &["applyUrl", "jobUrl"]);
+    let mut job = ats_job(AtsKind::Ashby, id, employer, apply, p);
+    job.job_title = first_str(p, &["title"]);
+    job.job_is_remote = p
+        .get("isRemote")
+        .and_then(|v| v.as_bool())
+        .or_else(|| first_str(p, &["workplaceType"]).map(|w| w.eq_ignore_ascii_case("remote")))
+        .or_else(|| location.as_deref().map(is_remote_text));
+    job.job_location = location;
+    job.job_description = first_str(p, &["descriptionHtml", "descriptionPlain"]);
+    if let Some(kind) = first_str(p, &["employmentType"]) {
+        job.job_employment_type = Some(employment_type_code(&kind));
+        job.job_employment_type_text = Some(kind);
+    }
+    job.job_posted_at_timestamp = posted.map(|d| d.timestamp());
+    job.job_posted_at_datetime_utc = posted;
+    Some(job)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn postings(text: &str) -> Vec<Value> {
+        let body: Value = serde_json::from_str(text).unwrap();
+        // Lever: array di root; Greenhouse / Ashby: {"jobs": [...]}
+        body.get("jobs").unwrap_or(&body).as_array().unwrap().clone()
+    }
+
+    #[test]
+    fn maps_greenhouse_posting() {
+        let postings = postings(include_str!("../fixtures/ats/greenhouse.json"));
+        let jobs: Vec<Job> = postings.iter().filter_map(|p| job_from_greenhouse(p, "Acme")).collect();
+        assert_eq!(jobs.len(), 2);
+
+        let job = &jobs[0];
+        assert_eq!(job.job_id, "gh_4567890");
+        assert_eq!(job.employer_name.as_deref(), Some("Acme"));
+        assert_eq!(job.job_publisher.as_deref(), Some("Greenhouse"));
+        assert_eq!(job.job_title.as_deref(), Some("Platform Engineer"));
+        assert_eq!(job.job_location.as_deref(), Some("Remote - US"));
+        assert_eq!(job.job_is_remote, Some(true));
+        assert_eq!(job.job_apply_link.as_deref(), Some("https://boards.greenhouse.io/acme/jobs/4567890"));
+        assert_eq!(job.job_apply_is_direct, Some(true));
+        // content di-escape oleh Greenhouse; hasilnya HTML biasa
+        assert_eq!(job.job_description.as_deref(), Some("<p>Run Kubernetes &amp; Terraform.</p>"));
+        assert_eq!(
+            job.job_posted_at_datetime_utc.map(|d| d.to_rfc3339()).as_deref(),
+            Some("2026-09-20T14:00:00+00:00")
+        );
+        assert_eq!(job.apply_options.len(), 1);
+
+        // tanpa first_published -> updated_at; lokasi kantor -> bukan remote
+        assert_eq!(jobs[1].job_is_remote, Some(false));
+        assert_eq!(
+            jobs[1].job_posted_at_datetime_utc.map(|d| d.to_rfc3339()).as_deref(),
+            Some("2026-09-22T10:15:00+00:00")
+        );
+    }
+
+    #[test]
+    fn maps_lever_posting() {
+        let postings = postings(include_str!("../fixtures/ats/lever.json"));
+        let jobs: Vec<Job> = postings.iter().filter_map(|p| job_from_lever(p, "Globex")).collect();
+        assert_eq!(jobs.len(), 2);
+
+        let job = &jobs[0];
+        assert_eq!(job.job_id, "lever_0f1e2d3c-aaaa-bbbb-cccc-1234567890ab");
+        assert_eq!(job.job_title.as_deref(), Some("Backend Engineer"));
+        assert_eq!(job.job_location.as_deref(), Some("Jakarta"));
+        // workplaceType menang atas teks lokasi
+        assert_eq!(job.job_is_remote, Some(false));
+        assert_eq!(job.job_employment_type.as_deref(), Some("FULLTIME"));
+        assert_eq!(job.job_employment_type_text.as_deref(), Some("Full-time"));
+        assert_eq!(job.job_apply_link.as_deref(), Some("https://jobs.lever.co/globex/0f1e2d3c/apply"));
+        let desc = job.job_description.as_deref().unwrap();
+        assert!(desc.starts_with("<p>Build APIs.</p>"));
+        assert!(desc.contains("<h3>Requirements</h3><ul><li>Rust</li><li>SQL</li></ul>"));
+        assert!(desc.ends_with("<p>Benefits apply.</p>"));
+        assert_eq!(job.job_posted_at_timestamp, Some(1_790_000_000));
+
+        // workplaceType "unspecified" -> teks lokasi; hostedUrl jika applyUrl kosong
+        let job = &jobs[1];
+        assert_eq!(job.job_is_remote, Some(true));
+        assert_eq!(job.job_apply_link.as_deref(), Some("https://jobs.lever.co/globex/9a8b7c6d"));
+        assert_eq!(job.job_description, None);
+    }
+
+    #[test]
+    fn maps_ashby_posting() {
+        let postings = postings(include_str!("../fixtures/ats/ashby.json"));
+        let jobs: Vec<Job> = postings.iter().filter_map(|p| job_from_ashby(p, "Initech")).collect();
+        // posting tanpa id dilewati
+        assert_eq!(jobs.len(), 2);
+
+        let job = &jobs[0];
+        assert_eq!(job.job_id, "ashby_7d6c5b4a-1111-2222-3333-444455556666");
+        assert_eq!(job.job_title.as_deref(), Some("Data Scientist"));
+        assert_eq!(job.job_location.as_deref(), Some("Berlin"));
+        assert_eq!(job.job_is_remote, Some(true));
+        assert_eq!(job.job_employment_type.as_deref(), Some("FULLTIME"));
+        assert_eq!(job.job_employment_type_text.as_deref(), Some("FullTime"));
+        assert_eq!(job.job_apply_link.as_deref(), Some("https://jobs.ashbyhq.com/initech/7d6c5b4a/application"));
+        assert_eq!(job.job_description.as_deref(), Some("<p>Model pricing.</p>"));
+        assert_eq!(
+            job.job_posted_at_datetime_utc.map(|d| d.to_rfc3339()).as_deref(),
+            Some("2026-09-25T08:00:00+00:00")
+        );
+
+        // tanpa isRemote -> workplaceType; tanpa descriptionHtml -> descriptionPlain; jobUrl
+        let job = &jobs[1];
+        assert_eq!(job.job_is_remote, Some(false));
+        assert_eq!(job.job_description.as_deref(), Some("Plain text description."));
+        assert_eq!(job.job_apply_link.as_deref(), Some("https://jobs.ashbyhq.com/initech/1234"));
+    }
+
+    #[test]
+    fn parses_company_list() {
+        assert_eq!(
+            AtsCompany::parse_list("Acme Corp=acme, globex\n=initech,"),
+            vec![
+                AtsCompany { slug: "acme".into(), name: Some("Acme Corp".into()) },
+                AtsCompany { slug: "globex".into(), name: None },
+                AtsCompany { slug: "initech".into(), name: None },
+            ]
+        );
+    }
+}
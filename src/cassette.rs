@@ -0,0 +1,288 @@
+// src/cassette.rs
+//
+// Rekam / putar ulang request-response provider (JSearch, LinkedIn, OpenAI) sebagai
+// "cassette" di disk. Mode record menyimpan body mentah utuh (bukan preview 1200
+// karakter), jadi payload yang gagal di-parse bisa diperiksa dan dijadikan kasus uji;
+// mode replay melayani response dari file tersebut tanpa jaringan.
+//
+// `HTTP_CASSETTE` = off (default) | record | replay | auto (replay jika ada, selain itu record)
+// `HTTP_CASSETTE_DIR` = folder cassette (default `cassettes`)
+//
+// Satu file per request: `<dir>/<provider>/<key>.json`, key = sha256(method, path + query, body).
+// Header request (API key) tidak ikut disimpan maupun dihitung ke key.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::{Request, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::retry::Retrier;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    Off,
+    Record,
+    Replay,
+    /// replay jika cassette ada, jika belum kirim request asli lalu rekam
+    Auto,
+}
+
+impl CassetteMode {
+    pub fn from_param(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "record" => CassetteMode::Record,
+            "replay" => CassetteMode::Replay,
+            "auto" => CassetteMode::Auto,
+            _ => CassetteMode::Off,
+        }
+    }
+}
+
+/// Isi satu file cassette.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CassetteEntry {
+    pub provider: String,
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub request_body: Option<String>,
+    pub status: u16,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// body response mentah, apa adanya
+    pub body: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Hasil request (asli atau dari cassette). Body sudah dibaca sebagai teks.
+#[derive(Debug, Clone)]
+pub struct Exchange {
+    pub status: StatusCode,
+    pub body: String,
+    /// file cassette yang ditulis / dibaca (None jika mode off)
+    pub cassette: Option<PathBuf>,
+}
+
+impl Exchange {
+    /// `Err` untuk status 4xx/5xx, dengan body (dipotong) di pesan error.
+    pub fn error_for_status(self) -> Result<Self> {
+        if self.status.is_client_error() || self.status.is_server_error() {
+            let mut preview: String = self.body.chars().take(500).collect();
+            if preview.len() < self.body.len() {
+                preview.push('…');
+            }
+            bail!("HTTP {}: {}{}", self.status, preview, self.cassette_hint());
+        }
+        Ok(self)
+    }
+
+    /// Tambahan untuk pesan error: lokasi body lengkap.
+    pub fn cassette_hint(&self) -> String {
+        match &self.cassette {
+            Some(p) => format!(" (body lengkap: {})", p.display()),
+            None => String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    mode: CassetteMode,
+    dir: PathBuf,
+}
+
+impl Default for Cassette {
+    fn default() -> Self {
+        Self { mode: CassetteMode::Off, dir: PathBuf::from("cassettes") }
+    }
+}
+
+impl Cassette {
+    pub fn new(mode: CassetteMode, dir: impl Into<PathBuf>) -> Self {
+        Self { mode, dir: dir.into() }
+    }
+
+    pub fn from_env() -> Self {
+        let mode = CassetteMode::from_param(&env::var("HTTP_CASSETTE").unwrap_or_default());
+        let dir = env::var("HTTP_CASSETTE_DIR")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| "cassettes".to_string());
+        Self::new(mode, dir)
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Kirim request lewat cassette. `retry` = Retrier client (RapidAPI); None = kirim langsung.
+    pub async fn send(&self, provider: &str, req: RequestBuilder, retry: Option<&Retrier>) -> Result<Exchange> {
+        if self.mode == CassetteMode::Off {
+            let resp = match retry {
+                Some(r) => r.send(req).await,
+                None => req.send().await,
+            }
+            .context("request failed")?;
+            let status = resp.status();
+            let body = resp.text().await.context("read response body")?;
+            return Ok(Exchange { status, body, cassette: None });
+        }
+
+        let (client, request) = req.build_split();
+        let request = request.context("build request")?;
+        let path = self.path_for(provider, &request);
+
+        if matches!(self.mode, CassetteMode::Replay | CassetteMode::Auto) && path.exists() {
+            let entry = read_entry(&path)?;
+            let status = StatusCode::from_u16(entry.status).unwrap_or(StatusCode::OK);
+            return Ok(Exchange { status, body: entry.body, cassette: Some(path) });
+        }
+        if self.mode == CassetteMode::Replay {
+            bail!(
+                "cassette tidak ditemukan untuk {} {}: {}",
+                request.method(),
+                request.url(),
+                path.display()
+            );
+        }
+
+        let mut entry = CassetteEntry {
+            provider: provider.to_string(),
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            request_body: request_body(&request),
+            status: 0,
+            content_type: None,
+            body: String::new(),
+            recorded_at: Utc::now(),
+        };
+
+        let req = RequestBuilder::from_parts(client, request);
+        let resp = match retry {
+            Some(r) => r.send(req).await,
+            None => req.send().await,
+        }
+        .context("request failed")?;
+        let status = resp.status();
+        entry.status = status.as_u16();
+        entry.content_type = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        entry.body = resp.text().await.context("read response body")?;
+
+        // gagal menulis cassette tidak boleh menggagalkan request asli
+        if let Err(e) = write_entry(&path, &entry) {
+            eprintln!("cassette: gagal menulis {}: {e:#}", path.display());
+        }
+        Ok(Exchange { status, body: entry.body, cassette: Some(path) })
+    }
+
+    /// `<dir>/<provider>/<key>.json`
+    fn path_for(&self, provider: &str, request: &Request) -> PathBuf {
+        self.dir.join(provider).join(format!("{}.json", request_key(request)))
+    }
+}
+
+/// 32 hex sha256 dari method, path + query, dan body request. Scheme/host tidak ikut,
+/// jadi cassette tetap cocok walau base URL berganti (mis. mock server di port lain).
+pub fn request_key(request: &Request) -> String {
+    let url = request.url();
+    let mut hasher = Sha256::new();
+    hasher.update(request.method().as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(url.path().as_bytes());
+    hasher.update(b"?");
+    hasher.update(url.query().unwrap_or("").as_bytes());
+    hasher.update(b"\n");
+    if let Some(body) = request.body().and_then(|b| b.as_bytes()) {
+        hasher.update(body);
+    }
+    hasher.finalize().iter().take(16).map(|b| format!("{b:02x}")).collect()
+}
+
+fn request_body(request: &Request) -> Option<String> {
+    request
+        .body()
+        .and_then(|b| b.as_bytes())
+        .map(|b| String::from_utf8_lossy(b).into_owned())
+}
+
+pub fn read_entry(path: &Path) -> Result<CassetteEntry> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("parse cassette {}", path.display()))
+}
+
+fn write_entry(path: &Path, entry: &CassetteEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_vec_pretty(entry)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_client::JobApiClient;
+    use crate::linkedin_client::{job_from_linkedin, LinkedInApiClient};
+
+    /// Cassette contoh yang di-commit (host `mock.invalid` tidak pernah dihubungi).
+    fn replay() -> Cassette {
+        Cassette::new(CassetteMode::Replay, "fixtures/cassettes")
+    }
+
+    #[test]
+    fn key_ignores_host_and_headers() {
+        let client = reqwest::Client::new();
+        let a = client.get("https://jsearch.p.rapidapi.com/search?query=rust").header("X-RapidAPI-Key", "a");
+        let b = client.get("http://127.0.0.1:8089/search?query=rust").header("X-RapidAPI-Key", "b");
+        let c = client.get("http://127.0.0.1:8089/search?query=go");
+        let key = |r: RequestBuilder| request_key(&r.build().unwrap());
+        assert_eq!(key(a), key(b.try_clone().unwrap()));
+        assert_ne!(key(b), key(c));
+    }
+
+    #[tokio::test]
+    async fn replays_jsearch_search_through_decode_body() {
+        let api = JobApiClient::new("test-key".into(), "jsearch.p.rapidapi.com".into())
+            .with_base_url("http://mock.invalid")
+            .with_cassette(replay());
+        // body cassette berisi JSON kedua setelah response; decode_body mengambil yang pertama
+        let resp = api.search("rust developer", 1, 1, "all", "us", "en").await.unwrap();
+        assert_eq!(resp.request_id.as_deref(), Some("mock-jsearch-search"));
+        assert_eq!(resp.data.len(), 3);
+        assert_eq!(resp.data[0].job_id, "mockJS001==");
+        assert_eq!(resp.data[0].job_title.as_deref(), Some("Senior Rust Engineer"));
+    }
+
+    #[tokio::test]
+    async fn replays_linkedin_get_through_mapper() {
+        let li = LinkedInApiClient::new("test-key".into())
+            .with_base_url("http://mock.invalid/v2/linkedin")
+            .with_cassette(replay());
+        let body = li.get_job("4012345678").await.unwrap();
+        let job = job_from_linkedin(body.get("data").unwrap()).unwrap();
+        assert_eq!(job.job_id, "li_4012345678");
+        assert_eq!(job.job_is_remote, Some(true));
+    }
+
+    #[tokio::test]
+    async fn replay_without_cassette_fails_with_path() {
+        let api = JobApiClient::new("test-key".into(), "jsearch.p.rapidapi.com".into())
+            .with_base_url("http://mock.invalid")
+            .with_cassette(replay());
+        let err = api.search("cobol", 1, 1, "all", "us", "en").await.unwrap_err().to_string();
+        assert!(err.contains("cassette tidak ditemukan"), "{err}");
+        assert!(err.contains("fixtures/cassettes/jsearch/"), "{err}");
+    }
+}
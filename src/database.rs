@@ -1,10 +1,13 @@
 // src/database.rs
 
-use crate::models::{ApplyOption, Job, Resume};
-use chrono::Utc;
-                       // ⬅️ add
+use crate::job_source::SearchRequest;
+use crate::link_checker::LinkStatus;
+use crate::models::{
+    ApiCall, ApplyOption, DuplicateJob, HostQuota, Job, JobListItem, Resume, RunNewJob, SalaryEstimate,
+    SavedSearch, SearchRun, SearchRunJob, UsageDay,
+};
+use chrono::{DateTime, Utc};
 use sqlx::{MySql, Pool};
-use sqlx::mysql::MySqlQueryResult;              // ⬅️ add
 
 
 
@@ -13,148 +16,251 @@ pub struct Database {
     pool: Pool<MySql>,
 }
 
-impl Database {
-    pub async fn new(database_url: &str) -> Result<Self, sqlx::Error> {
-        let pool = Pool::connect(database_url).await?;
-        Ok(Database { pool })
+/// Normalisasi kunci salary_estimates di sisi SQL; harus sama dengan `salary::normalize_key`
+/// + `salary::estimate_location`.
+const SALARY_TITLE_KEY_SQL: &str = "LEFT(LOWER(TRIM(j.job_title)), 255)";
+const SALARY_LOCATION_KEY_SQL: &str =
+    "LEFT(LOWER(TRIM(COALESCE(NULLIF(TRIM(j.job_location), ''), j.job_country, ''))), 255)";
+
+/// Job yang tampil di /list dan facet: kanonik, atau duplikat yang kanoniknya sudah expired
+/// (posting yang masih hidup tidak ikut hilang bersama kanoniknya). Alias `j`.
+const LISTED_JOB_SQL: &str = "(j.canonical_job_id IS NULL OR NOT EXISTS (
+    SELECT 1 FROM jobs c WHERE c.job_id = j.canonical_job_id AND c.expired_at IS NULL))";
+
+/// Seperti `LISTED_JOB_SQL` untuk mode "include expired": kanonik yang expired pun tampil,
+/// jadi duplikatnya tetap disembunyikan.
+const LISTED_JOB_INCLUDING_EXPIRED_SQL: &str = "(j.canonical_job_id IS NULL OR NOT EXISTS (
+    SELECT 1 FROM jobs c WHERE c.job_id = j.canonical_job_id))";
+
+/// Urutan daftar /list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListSort {
+    #[default]
+    Updated,
+    /// gaji tahunan tertinggi dulu; job tanpa gaji tahunan (periode/kurs tidak diketahui)
+    /// menyusul, diurutkan gaji posting mentah lalu estimasi; tanpa gaji di akhir
+    Salary,
+    /// gaji tahunan terendah dulu; tanpa gaji tahunan di akhir
+    SalaryAsc,
+}
+
+impl ListSort {
+    pub fn from_param(s: Option<&str>) -> Self {
+        match s {
+            Some("salary") => ListSort::Salary,
+            Some("salary_asc") => ListSort::SalaryAsc,
+            _ => ListSort::Updated,
+        }
     }
 
-    // src/database.rs
-pub async fn upsert_job_from_linkedin(
-    &self,
-    detail_obj: &serde_json::Value,
-) -> sqlx::Result<()> {
-    // Ambil field dari JSON LinkedIn
-    let id           = detail_obj.get("id").and_then(|v| v.as_str()).unwrap_or_default();
-    let job_id       = format!("li_{id}");
-    let title        = detail_obj.get("title").and_then(|v| v.as_str()).unwrap_or_default();
-    let company      = detail_obj.get("companyName").and_then(|v| v.as_str()).unwrap_or_default();
-    let location     = detail_obj.get("location").and_then(|v| v.as_str()).unwrap_or_default();
-
-    let _li_url = detail_obj
-    .get("linkedinUrl")
-    .and_then(|v| v.as_str());
-    // >>> INI YANG KAMU TANYAKAN:
-    let desc = detail_obj
-        .get("description")
-        .and_then(|v| v.as_str())
-        .unwrap_or_default()
-        .to_string();
-
-    // (opsional) url apply
-    let li_url = detail_obj
-        .get("linkedinUrl")
-        .and_then(|v| v.as_str());
-
-    // Simpan ke DB (contoh MySQL + sqlx)
-    sqlx::query(r#"
-        INSERT INTO jobs (job_id, job_title, employer_name, job_location, job_description)
-        VALUES (?, ?, ?, ?, ?)
-        ON DUPLICATE KEY UPDATE
-            job_title=VALUES(job_title),
-            employer_name=VALUES(employer_name),
-            job_location=VALUES(job_location),
-            job_description=VALUES(job_description)
-    "#)
-        .bind(&job_id)
-        .bind(title)
-        .bind(company)
-        .bind(location)
-        .bind(&desc)
-        .execute(&self.pool)
-        .await?;
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            ListSort::Updated => "",
+            ListSort::Salary => "salary",
+            ListSort::SalaryAsc => "salary_asc",
+        }
+    }
 
-    Ok(())
+    fn order_by(&self) -> &'static str {
+        match self {
+            ListSort::Updated => "j.updated_at DESC, j.job_posted_at_timestamp DESC",
+            ListSort::Salary => {
+                "COALESCE(j.salary_annual_max, j.salary_annual_min) IS NULL, \
+                 COALESCE(j.salary_annual_max, j.salary_annual_min) DESC, \
+                 j.updated_at DESC"
+            }
+            ListSort::SalaryAsc => {
+                "COALESCE(j.salary_annual_min, j.salary_annual_max) IS NULL, \
+                 COALESCE(j.salary_annual_min, j.salary_annual_max) ASC, \
+                 j.updated_at DESC"
+            }
+        }
+    }
 }
 
-    pub async fn insert_apply_option_if_new(
-        &self,
-        job_id: &str,
-        publisher: &str,
-        apply_link: Option<String>,
-        is_direct: Option<bool>,
-    ) -> Result<MySqlQueryResult, sqlx::Error> {
-        // Sederhana: insert saja (bisa ditambah unique index kalau mau)
-        sqlx::query!(
-            r#"
-            INSERT INTO job_apply_options (job_id, publisher, apply_link, is_direct, created_at)
-            VALUES (?, ?, ?, ?, NOW())
-            "#,
-            job_id,
-            publisher,
-            apply_link,
-            is_direct
-        )
-        .execute(&self.pool)
-        .await
+/// Filter /list, dipakai bersama oleh `count_jobs` dan `list_jobs_paged`.
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    /// cocok ke judul / perusahaan / lokasi
+    pub query: Option<String>,
+    /// tampilkan juga job yang sudah expired (default disembunyikan)
+    pub include_expired: bool,
+    /// rentang gaji tahunan (`SALARY_BASE_CURRENCY`) yang harus beririsan dengan rentang job;
+    /// job tanpa gaji tahunan tidak ikut jika salah satu diisi
+    pub salary_min: Option<u64>,
+    pub salary_max: Option<u64>,
+    /// nama skill kanonik (tabel job_skills)
+    pub skill: Option<String>,
+    /// kode bahasa posting ("en", "id", ...; dari sumber atau hasil deteksi)
+    pub language: Option<String>,
+    /// "remote" | "hybrid" | "onsite" (lihat `workplace`)
+    pub workplace: Option<String>,
+    /// wilayah remote ("Worldwide", "US", ...); job dengan beberapa wilayah cocok jika salah satunya sama
+    pub region: Option<String>,
+}
+
+impl ListFilter {
+    pub fn from_query(q: &std::collections::HashMap<String, String>) -> Self {
+        let flag = |k: &str| q.get(k).map(|v| v == "1" || v == "on" || v == "true").unwrap_or(false);
+        // "50.000" / "50,000" / "50 000" -> 50000
+        let amount = |k: &str| {
+            q.get(k)
+                .map(|v| v.chars().filter(|c| c.is_ascii_digit()).collect::<String>())
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|v| *v > 0)
+        };
+        ListFilter {
+            query: q.get("q").map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+            include_expired: flag("include_expired"),
+            salary_min: amount("salary_min"),
+            salary_max: amount("salary_max"),
+            skill: q.get("skill").map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+            language: q.get("lang").map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()),
+            workplace: q
+                .get("workplace")
+                .and_then(|s| crate::workplace::WorkplaceType::from_param(s))
+                .map(|t| t.as_str().to_string()),
+            region: q.get("region").map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+        }
     }
 
+    /// Query string filter aktif (tanpa page/sort) untuk link pagination & sort.
+    pub fn to_query_string(&self) -> String {
+        let mut parts = vec![format!("q={}", urlencoding::encode(self.query.as_deref().unwrap_or("")))];
+        if self.include_expired {
+            parts.push("include_expired=1".to_string());
+        }
+        if let Some(v) = self.salary_min {
+            parts.push(format!("salary_min={v}"));
+        }
+        if let Some(v) = self.salary_max {
+            parts.push(format!("salary_max={v}"));
+        }
+        if let Some(skill) = &self.skill {
+            parts.push(format!("skill={}", urlencoding::encode(skill)));
+        }
+        if let Some(lang) = &self.language {
+            parts.push(format!("lang={}", urlencoding::encode(lang)));
+        }
+        if let Some(workplace) = &self.workplace {
+            parts.push(format!("workplace={workplace}"));
+        }
+        if let Some(region) = &self.region {
+            parts.push(format!("region={}", urlencoding::encode(region)));
+        }
+        parts.join("&")
+    }
 
-    // Tambahan di impl Database
-pub async fn count_jobs(&self, query: Option<&str>) -> Result<i64, sqlx::Error> {
-    if let Some(q) = query {
-        let like = format!("%{}%", q);
-        sqlx::query_scalar::<_, i64>(
-            r#"SELECT COUNT(*) FROM jobs
-               WHERE job_title LIKE ?
-                  OR employer_name LIKE ?
-                  OR job_location LIKE ?"#,
-        )
-        .bind(&like)
-        .bind(&like)
-        .bind(&like)
-        .fetch_one(&self.pool)
-        .await
-    } else {
-        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM jobs"#)
-            .fetch_one(&self.pool)
-            .await
+    /// Klausa WHERE (alias `j`) + nilai bind-nya, urut sesuai placeholder.
+    fn where_sql(&self) -> (String, Vec<String>) {
+        // duplikat lintas sumber disembunyikan selama kanoniknya tampil (lihat `dedup`)
+        let mut conds = Vec::new();
+        let mut binds = Vec::new();
+        if self.include_expired {
+            conds.push(LISTED_JOB_INCLUDING_EXPIRED_SQL.to_string());
+        } else {
+            conds.push(LISTED_JOB_SQL.to_string());
+            conds.push("j.expired_at IS NULL".to_string());
+        }
+        if let Some(q) = &self.query {
+            conds.push("(j.job_title LIKE ? OR j.employer_name LIKE ? OR j.job_location LIKE ?)".to_string());
+            let like = format!("%{q}%");
+            binds.extend([like.clone(), like.clone(), like]);
+        }
+        if let Some(min) = self.salary_min {
+            conds.push("COALESCE(j.salary_annual_max, j.salary_annual_min) >= ?".to_string());
+            binds.push(min.to_string());
+        }
+        if let Some(max) = self.salary_max {
+            conds.push("COALESCE(j.salary_annual_min, j.salary_annual_max) <= ?".to_string());
+            binds.push(max.to_string());
+        }
+        if let Some(skill) = &self.skill {
+            conds.push("EXISTS (SELECT 1 FROM job_skills k WHERE k.job_id = j.job_id AND k.skill = ?)".to_string());
+            binds.push(skill.clone());
+        }
+        if let Some(lang) = &self.language {
+            conds.push("j.job_posting_language = ?".to_string());
+            binds.push(lang.clone());
+        }
+        if let Some(workplace) = &self.workplace {
+            conds.push("j.workplace_type = ?".to_string());
+            binds.push(workplace.clone());
+        }
+        if let Some(region) = &self.region {
+            // nilai tersimpan bisa gabungan "US, Canada"
+            conds.push("CONCAT(', ', j.remote_region_restriction, ', ') LIKE ?".to_string());
+            binds.push(format!("%, {region}, %"));
+        }
+        (format!("WHERE {}", conds.join(" AND ")), binds)
     }
 }
 
-pub async fn list_jobs_paged(
-    &self,
-    query: Option<&str>,
-    page: i64,
-    per_page: i64,
-) -> Result<Vec<Job>, sqlx::Error> {
-    let page = page.max(1);
-    let offset = (page - 1) * per_page;
+/// Awal hari UTC `days_ago` hari lalu, untuk dibandingkan dengan `api_calls.created_at`
+/// (ditulis dengan `UTC_TIMESTAMP()`).
+fn utc_day_start(days_ago: i64) -> chrono::NaiveDateTime {
+    (Utc::now().date_naive() - chrono::Duration::days(days_ago))
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+}
 
-    if let Some(q) = query {
-        let like = format!("%{}%", q);
-        sqlx::query_as::<_, Job>(
-            r#"
-            SELECT * FROM jobs
-            WHERE job_title LIKE ?
-               OR employer_name LIKE ?
-               OR job_location LIKE ?
-            ORDER BY updated_at DESC, job_posted_at_timestamp DESC
-            LIMIT ? OFFSET ?
-            "#,
-        )
-        .bind(&like)
-        .bind(&like)
-        .bind(&like)
-        .bind(per_page)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await
-    } else {
-        sqlx::query_as::<_, Job>(
+impl Database {
+    pub async fn new(database_url: &str) -> Result<Self, sqlx::Error> {
+        let pool = Pool::connect(database_url).await?;
+        Ok(Database { pool })
+    }
+
+    pub async fn count_jobs(&self, filter: &ListFilter) -> Result<i64, sqlx::Error> {
+        let (where_clause, binds) = filter.where_sql();
+        let sql = format!("SELECT COUNT(*) FROM jobs j {where_clause}");
+        let mut q = sqlx::query_scalar::<_, i64>(&sql);
+        for b in binds {
+            q = q.bind(b);
+        }
+        q.fetch_one(&self.pool).await
+    }
+
+    pub async fn list_jobs_paged(
+        &self,
+        filter: &ListFilter,
+        sort: ListSort,
+        page: i64,
+        per_page: i64,
+    ) -> Result<Vec<JobListItem>, sqlx::Error> {
+        let page = page.max(1);
+        let offset = (page - 1) * per_page;
+
+        // job kanonik membawa jumlah duplikatnya
+        let (where_clause, binds) = filter.where_sql();
+        let sql = format!(
             r#"
-            SELECT * FROM jobs
-            ORDER BY updated_at DESC, job_posted_at_timestamp DESC
+            SELECT j.*,
+                   s.min_salary      AS est_min_salary,
+                   s.max_salary      AS est_max_salary,
+                   s.median_salary   AS est_median_salary,
+                   s.salary_currency AS est_salary_currency,
+                   s.salary_period   AS est_salary_period,
+                   (SELECT COUNT(*) FROM jobs d WHERE d.canonical_job_id = j.job_id) AS duplicate_count,
+                   (SELECT COUNT(*) FROM job_apply_options o WHERE o.job_id = j.job_id) AS link_count,
+                   (SELECT COUNT(*) FROM job_apply_options o
+                     WHERE o.job_id = j.job_id AND o.link_alive = 0) AS dead_link_count
+              FROM jobs j
+              LEFT JOIN salary_estimates s
+                ON s.title_key = {SALARY_TITLE_KEY_SQL}
+               AND s.location_key = {SALARY_LOCATION_KEY_SQL}
+            {where_clause}
+            ORDER BY {order}
             LIMIT ? OFFSET ?
             "#,
-        )
-        .bind(per_page)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await
-    }
-}
+            order = sort.order_by(),
+        );
 
+        let mut q = sqlx::query_as::<_, JobListItem>(&sql);
+        for b in binds {
+            q = q.bind(b);
+        }
+        q.bind(per_page).bind(offset).fetch_all(&self.pool).await
+    }
 
     pub async fn get_all_jobs(&self) -> Result<Vec<Job>, sqlx::Error> {
         // MySQL: gunakan ? (bukan $1)
@@ -187,7 +293,17 @@ pub async fn list_jobs_paged(
     }
 
     /// Insert or update a job row (upsert).
-    pub async fn upsert_job(&self, job: &Job) -> Result<(), sqlx::Error> {
+    /// Returns `true` when the row is new. Existence is checked with `SELECT ... FOR UPDATE` in
+    /// the same transaction: sqlx connects with CLIENT_FOUND_ROWS, so `rows_affected()` is also 1
+    /// when an existing row is rewritten with identical values (same job twice within a second).
+    pub async fn upsert_job(&self, job: &Job) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let existed = sqlx::query(r#"SELECT 1 FROM jobs WHERE job_id = ? FOR UPDATE"#)
+            .bind(&job.job_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .is_some();
+
         sqlx::query!(
             r#"
             INSERT INTO jobs (
@@ -195,7 +311,7 @@ pub async fn list_jobs_paged(
                 employer_website, employer_company_type, employer_linkedin,
                 job_publisher, job_employment_type, job_employment_type_text,
                 job_employment_types_json, job_title, job_apply_link,
-                job_apply_is_direct, job_apply_quality_score, job_description,
+                job_apply_is_direct, job_apply_quality_score, job_description, job_description_raw,
                 job_is_remote, job_posted_human_readable, job_posted_at_timestamp,
                 job_posted_at_datetime_utc, job_location, job_city, job_state,
                 job_country, job_latitude, job_longitude, job_benefits_json,
@@ -206,11 +322,11 @@ pub async fn list_jobs_paged(
                 job_max_salary, job_salary_currency, job_salary_period,
                 job_highlights_json, job_job_title, job_posting_language,
                 job_onet_soc, job_onet_job_zone, raw_json,
-                created_at, updated_at, matching_analysis, cover_letter, isdelete
+                created_at, updated_at, last_seen_at, matching_analysis, cover_letter, isdelete
             ) VALUES (
                 ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
                 ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
-                ?, ?, ?, ?, ?, ?, ?, ?, 0
+                ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0
             )
             ON DUPLICATE KEY UPDATE
                 request_id = VALUES(request_id),
@@ -228,7 +344,8 @@ pub async fn list_jobs_paged(
                 job_apply_link = VALUES(job_apply_link),
                 job_apply_is_direct = VALUES(job_apply_is_direct),
                 job_apply_quality_score = VALUES(job_apply_quality_score),
-                job_description = VALUES(job_description),
+                job_description = COALESCE(VALUES(job_description), job_description),
+                job_description_raw = COALESCE(VALUES(job_description_raw), job_description_raw),
                 job_is_remote = VALUES(job_is_remote),
                 job_posted_human_readable = VALUES(job_posted_human_readable),
                 job_posted_at_timestamp = VALUES(job_posted_at_timestamp),
@@ -254,12 +371,18 @@ pub async fn list_jobs_paged(
                 job_salary_period = VALUES(job_salary_period),
                 job_highlights_json = VALUES(job_highlights_json),
                 job_job_title = VALUES(job_job_title),
-                job_posting_language = VALUES(job_posting_language),
+                -- bahasa hasil deteksi tidak dihapus oleh upsert tanpa deskripsi
+                job_posting_language = COALESCE(VALUES(job_posting_language), job_posting_language),
                 job_onet_soc = VALUES(job_onet_soc),
                 job_onet_job_zone = VALUES(job_onet_job_zone),
                 raw_json = VALUES(raw_json),
-                matching_analysis = VALUES(matching_analysis),
-                cover_letter = VALUES(cover_letter),
+                -- hasil AI tidak dikirim sumber job: jangan ditimpa dengan kosong
+                matching_analysis = IF(VALUES(matching_analysis) = '', matching_analysis, VALUES(matching_analysis)),
+                cover_letter = COALESCE(VALUES(cover_letter), cover_letter),
+                -- terlihat lagi di sumber -> bukan "hilang" lagi
+                upstream_missing_at = NULL,
+                -- last_seen_at / expired_at hanya diubah ingestion search (`mark_job_seen`),
+                -- bukan oleh refresh, import atau lazy enrich halaman detail
                 updated_at = VALUES(updated_at)
             "#,
             job.job_id,
@@ -279,6 +402,7 @@ pub async fn list_jobs_paged(
             job.job_apply_is_direct,
             job.job_apply_quality_score,
             job.job_description,
+            job.job_description_raw,
             job.job_is_remote,
             job.job_posted_human_readable,
             job.job_posted_at_timestamp,
@@ -310,20 +434,23 @@ pub async fn list_jobs_paged(
             job.raw_json,
             Utc::now(),
             Utc::now(),
+            Utc::now(),
             job.matching_analysis,
             job.cover_letter,
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(())
+        tx.commit().await?;
+        Ok(!existed)
     }
 
     pub async fn upsert_apply_option(&self, option: &ApplyOption) -> Result<(), sqlx::Error> {
         sqlx::query!(
             r#"
-            INSERT IGNORE INTO job_apply_options (job_id, publisher, apply_link, is_direct)
+            INSERT INTO job_apply_options (job_id, publisher, apply_link, is_direct)
             VALUES (?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE copied_from_job_id = NULL
             "#,
             option.job_id,
             option.publisher,
@@ -335,6 +462,651 @@ pub async fn list_jobs_paged(
         Ok(())
     }
 
+    // ==================== link checker ====================
+
+    /// Opsi apply yang belum pernah dicek atau terakhir dicek sebelum `older_than`
+    /// (yang belum pernah dicek duluan). Job expired / duplikat dilewati.
+    pub async fn apply_options_due_for_check(
+        &self,
+        older_than: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<(u64, String)>, sqlx::Error> {
+        sqlx::query_as::<_, (u64, String)>(
+            r#"
+            SELECT o.id, o.apply_link
+              FROM job_apply_options o
+              JOIN jobs j ON j.job_id = o.job_id
+             WHERE o.apply_link LIKE 'http%'
+               AND j.expired_at IS NULL
+               AND j.canonical_job_id IS NULL
+               AND (o.link_checked_at IS NULL OR o.link_checked_at < ?)
+             ORDER BY o.link_checked_at IS NOT NULL, o.link_checked_at ASC, o.id ASC
+             LIMIT ?
+            "#,
+        )
+        .bind(older_than)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Simpan hasil cek. Host tak terjangkau menambah `link_fail_count` dan baru menjadi
+    /// `link_alive = 0` setelah `dead_after` kali berturut-turut; hasil lain me-reset hitungan.
+    /// Return `link_alive` yang tersimpan.
+    pub async fn record_link_check(
+        &self,
+        option_id: u64,
+        status: &LinkStatus,
+        dead_after: u32,
+    ) -> Result<Option<bool>, sqlx::Error> {
+        // MySQL mengevaluasi SET berurutan: link_alive melihat link_fail_count yang baru
+        sqlx::query(
+            r#"
+            UPDATE job_apply_options
+               SET link_status = ?, link_final_url = ?, link_error = ?, link_checked_at = ?,
+                   link_fail_count = IF(?, link_fail_count + 1, 0),
+                   link_alive = IF(?, IF(link_fail_count >= ?, 0, NULL), ?)
+             WHERE id = ?
+            "#,
+        )
+        .bind(status.status)
+        .bind(status.final_url.as_deref().map(|u| u.chars().take(1024).collect::<String>()))
+        .bind(status.error.as_deref())
+        .bind(Utc::now())
+        .bind(status.unreachable)
+        .bind(status.unreachable)
+        .bind(dead_after)
+        .bind(status.alive)
+        .bind(option_id)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query_scalar::<_, Option<bool>>(r#"SELECT link_alive FROM job_apply_options WHERE id = ?"#)
+            .bind(option_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map(Option::flatten)
+    }
+
+    // ==================== expiry ====================
+
+    /// Tandai expired job yang tanggal kedaluwarsa postingnya sudah lewat.
+    pub async fn expire_past_offer_date(&self, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let res = sqlx::query(
+            r#"
+            UPDATE jobs
+               SET expired_at = ?, expired_reason = 'expiration', updated_at = updated_at
+             WHERE expired_at IS NULL
+               AND (job_offer_expiration_datetime_utc < ?
+                    OR (job_offer_expiration_datetime_utc IS NULL AND job_offer_expiration_timestamp < ?))
+            "#,
+        )
+        .bind(now)
+        .bind(now)
+        .bind(now.timestamp())
+        .execute(&self.pool)
+        .await?;
+        Ok(res.rows_affected())
+    }
+
+    /// Tandai expired job yang tidak muncul di pencarian mana pun sejak `cutoff`
+    /// (baris lama tanpa last_seen_at memakai updated_at).
+    pub async fn expire_not_seen_since(&self, cutoff: DateTime<Utc>, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let res = sqlx::query(
+            r#"
+            UPDATE jobs
+               SET expired_at = ?, expired_reason = 'not_seen', updated_at = updated_at
+             WHERE expired_at IS NULL
+               AND COALESCE(last_seen_at, updated_at) < ?
+            "#,
+        )
+        .bind(now)
+        .bind(cutoff)
+        .execute(&self.pool)
+        .await?;
+        Ok(res.rows_affected())
+    }
+
+    // ==================== dedup lintas sumber ====================
+
+    /// Job kanonik lain dengan fingerprint sama (terlama dulu): (job_id, deskripsi).
+    pub async fn dedup_candidates(
+        &self,
+        fingerprint: &str,
+        exclude_job_id: &str,
+    ) -> Result<Vec<(String, Option<String>)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, Option<String>)>(
+            r#"
+            SELECT job_id, job_description FROM jobs
+             WHERE dedup_fingerprint = ?
+               AND job_id <> ?
+               AND canonical_job_id IS NULL
+             ORDER BY created_at ASC, job_id ASC
+             LIMIT 20
+            "#,
+        )
+        .bind(fingerprint)
+        .bind(exclude_job_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Deskripsi yang tersimpan (upsert tanpa deskripsi mempertahankan yang lama).
+    pub async fn stored_job_description(&self, job_id: &str) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar::<_, Option<String>>(r#"SELECT job_description FROM jobs WHERE job_id = ?"#)
+            .bind(job_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map(Option::flatten)
+    }
+
+    /// Metadata dedup; `updated_at` sengaja tidak disentuh (`job_finder dedup` menyentuh semua baris).
+    pub async fn set_job_dedup(
+        &self,
+        job_id: &str,
+        fingerprint: Option<&str>,
+        canonical_job_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"UPDATE jobs SET dedup_fingerprint = ?, canonical_job_id = ?, updated_at = updated_at WHERE job_id = ?"#,
+        )
+            .bind(fingerprint)
+            .bind(canonical_job_id)
+            .bind(job_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Gabungkan `duplicate_id` ke `canonical_id`: duplikat milik job ini ikut dipindah
+    /// (berikut salinan opsi apply mereka), opsi apply + link apply utamanya disalin ke job
+    /// kanonik dengan `copied_from_job_id` (uniq_job_link mencegah dobel).
+    pub async fn merge_duplicate_into(&self, duplicate_id: &str, canonical_id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(r#"UPDATE jobs SET canonical_job_id = ?, updated_at = updated_at WHERE canonical_job_id = ?"#)
+            .bind(canonical_id)
+            .bind(duplicate_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"UPDATE IGNORE job_apply_options SET job_id = ? WHERE job_id = ? AND copied_from_job_id IS NOT NULL"#,
+        )
+        .bind(canonical_id)
+        .bind(duplicate_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(r#"DELETE FROM job_apply_options WHERE job_id = ? AND copied_from_job_id IS NOT NULL"#)
+            .bind(duplicate_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT IGNORE INTO job_apply_options (job_id, publisher, apply_link, is_direct, copied_from_job_id)
+            SELECT ?, publisher, apply_link, is_direct, job_id
+              FROM job_apply_options
+             WHERE job_id = ? AND copied_from_job_id IS NULL
+            "#,
+        )
+        .bind(canonical_id)
+        .bind(duplicate_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT IGNORE INTO job_apply_options (job_id, publisher, apply_link, is_direct, copied_from_job_id)
+            SELECT ?, job_publisher, job_apply_link, job_apply_is_direct, job_id
+              FROM jobs
+             WHERE job_id = ?
+               AND job_apply_link IS NOT NULL AND job_apply_link <> ''
+            "#,
+        )
+        .bind(canonical_id)
+        .bind(duplicate_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await
+    }
+
+    /// Hapus opsi apply yang pernah disalin dari `job_id` ke job kanonik mana pun.
+    pub async fn remove_copied_apply_options(&self, job_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(r#"DELETE FROM job_apply_options WHERE copied_from_job_id = ?"#)
+            .bind(job_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Duplikat yang ditautkan ke `canonical_id` (untuk halaman detail).
+    pub async fn job_duplicates(&self, canonical_id: &str) -> Result<Vec<DuplicateJob>, sqlx::Error> {
+        sqlx::query_as::<_, DuplicateJob>(
+            r#"
+            SELECT job_id, job_title, job_publisher, job_apply_link
+              FROM jobs
+             WHERE canonical_job_id = ?
+             ORDER BY created_at ASC
+            "#,
+        )
+        .bind(canonical_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Simpan hasil `salary::annualize`; `updated_at` tidak disentuh (`salary-normalize`).
+    pub async fn set_job_salary_annual(
+        &self,
+        job_id: &str,
+        annual: Option<&crate::salary::AnnualSalary>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"UPDATE jobs SET salary_annual_min = ?, salary_annual_max = ?, salary_annual_currency = ?,
+                       updated_at = updated_at
+                WHERE job_id = ?"#,
+        )
+        .bind(annual.and_then(|a| a.min.clone()))
+        .bind(annual.and_then(|a| a.max.clone()))
+        .bind(annual.map(|a| a.currency.as_str()))
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Job dengan gaji posting, atau yang masih punya gaji tahunan lama (untuk dikosongkan).
+    pub async fn jobs_with_posted_salary(&self) -> Result<Vec<Job>, sqlx::Error> {
+        sqlx::query_as::<_, Job>(
+            r#"
+            SELECT * FROM jobs
+             WHERE job_min_salary IS NOT NULL
+                OR job_max_salary IS NOT NULL
+                OR salary_annual_currency IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Simpan hasil `workplace::classify`; `job_is_remote` (data sumber) dan `updated_at` tidak disentuh.
+    /// `keep_existing`: hasil kosong tidak menimpa nilai lama (upsert tanpa deskripsi).
+    pub async fn set_job_workplace(
+        &self,
+        job_id: &str,
+        wp: &crate::workplace::Workplace,
+        keep_existing: bool,
+    ) -> Result<(), sqlx::Error> {
+        // MySQL mengevaluasi SET dari kiri ke kanan: `workplace_type` di baris kedua sudah nilai baru
+        let sql = if keep_existing {
+            r#"
+            UPDATE jobs
+               SET workplace_type = COALESCE(?, workplace_type),
+                   remote_region_restriction =
+                       COALESCE(?, IF(workplace_type = 'remote', remote_region_restriction, NULL)),
+                   updated_at = updated_at
+             WHERE job_id = ?
+            "#
+        } else {
+            r#"
+            UPDATE jobs
+               SET workplace_type = ?,
+                   remote_region_restriction = ?,
+                   updated_at = updated_at
+             WHERE job_id = ?
+            "#
+        };
+        let workplace_type = wp.workplace_type.map(|t| t.as_str());
+        sqlx::query(sql)
+            .bind(workplace_type)
+            .bind(wp.remote_region_restriction.as_deref())
+            .bind(job_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// (workplace_type, remote_region_restriction, jumlah) job kanonik yang belum expired.
+    pub async fn workplace_counts(&self) -> Result<Vec<(String, Option<String>, i64)>, sqlx::Error> {
+        let sql = format!(
+            r#"
+            SELECT workplace_type, remote_region_restriction, COUNT(*) AS n
+              FROM jobs j
+             WHERE {LISTED_JOB_SQL} AND j.expired_at IS NULL
+               AND j.workplace_type IS NOT NULL
+             GROUP BY workplace_type, remote_region_restriction
+            "#
+        );
+        sqlx::query_as::<_, (String, Option<String>, i64)>(&sql).fetch_all(&self.pool).await
+    }
+
+    pub async fn job_ids_without_language(&self) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar::<_, String>(
+            r#"SELECT job_id FROM jobs WHERE job_posting_language IS NULL OR job_posting_language = ''"#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Hasil `job_finder language`; `updated_at` tidak disentuh.
+    pub async fn set_job_language(&self, job_id: &str, language: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(r#"UPDATE jobs SET job_posting_language = ?, updated_at = updated_at WHERE job_id = ?"#)
+            .bind(language)
+            .bind(job_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Bahasa posting job kanonik yang belum expired (pilihan filter /list).
+    pub async fn language_counts(&self) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        let sql = format!(
+            r#"
+            SELECT job_posting_language, COUNT(*) AS n
+              FROM jobs j
+             WHERE {LISTED_JOB_SQL} AND j.expired_at IS NULL
+               AND j.job_posting_language IS NOT NULL AND j.job_posting_language <> ''
+             GROUP BY job_posting_language
+             ORDER BY n DESC
+            "#
+        );
+        sqlx::query_as::<_, (String, i64)>(&sql).fetch_all(&self.pool).await
+    }
+
+    /// Ganti seluruh skill job (hasil `skills::store`).
+    pub async fn replace_job_skills(&self, job_id: &str, skills: &[String]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(r#"DELETE FROM job_skills WHERE job_id = ?"#)
+            .bind(job_id)
+            .execute(&mut *tx)
+            .await?;
+        if !skills.is_empty() {
+            let placeholders = vec!["(?, ?)"; skills.len()].join(", ");
+            let sql = format!("INSERT IGNORE INTO job_skills (job_id, skill) VALUES {placeholders}");
+            let mut q = sqlx::query(&sql);
+            for skill in skills {
+                q = q.bind(job_id).bind(skill);
+            }
+            q.execute(&mut *tx).await?;
+        }
+        tx.commit().await
+    }
+
+    pub async fn job_skills(&self, job_id: &str) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar::<_, String>(r#"SELECT skill FROM job_skills WHERE job_id = ? ORDER BY skill"#)
+            .bind(job_id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Skill terbanyak di job kanonik yang belum expired (pilihan filter /list).
+    pub async fn skill_counts(&self, limit: i64) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        let sql = format!(
+            r#"
+            SELECT k.skill, COUNT(*) AS n
+              FROM job_skills k
+              JOIN jobs j ON j.job_id = k.job_id
+             WHERE {LISTED_JOB_SQL} AND j.expired_at IS NULL
+             GROUP BY k.skill
+             ORDER BY n DESC, k.skill
+             LIMIT ?
+            "#
+        );
+        sqlx::query_as::<_, (String, i64)>(&sql).bind(limit).fetch_all(&self.pool).await
+    }
+
+    pub async fn reset_job_dedup(&self) -> Result<(), sqlx::Error> {
+        sqlx::query(r#"DELETE FROM job_apply_options WHERE copied_from_job_id IS NOT NULL"#)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(r#"UPDATE jobs SET dedup_fingerprint = NULL, canonical_job_id = NULL, updated_at = updated_at"#)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn job_ids_oldest_first(&self) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar::<_, String>(r#"SELECT job_id FROM jobs ORDER BY created_at ASC, job_id ASC"#)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Subset `job_ids` yang sudah tersimpan dengan deskripsi tidak kosong.
+    pub async fn job_ids_with_description(
+        &self,
+        job_ids: &[String],
+    ) -> Result<std::collections::HashSet<String>, sqlx::Error> {
+        if job_ids.is_empty() {
+            return Ok(Default::default());
+        }
+        let placeholders = vec!["?"; job_ids.len()].join(", ");
+        let sql = format!(
+            "SELECT job_id FROM jobs
+              WHERE job_id IN ({placeholders})
+                AND job_description IS NOT NULL
+                AND job_description <> ''"
+        );
+        let mut q = sqlx::query_scalar::<_, String>(&sql);
+        for id in job_ids {
+            q = q.bind(id);
+        }
+        Ok(q.fetch_all(&self.pool).await?.into_iter().collect())
+    }
+
+    /// Catat job terlihat di hasil search: geser last_seen_at dan aktifkan lagi job expired,
+    /// kecuali expiry 'expiration' yang tanggal kedaluwarsa postingnya masih lewat.
+    /// Untuk job yang tidak di-enrich ulang (sudah lengkap di DB) ini satu-satunya tulisan:
+    /// upsert dengan record search yang tipis akan menimpa kolom hasil enrichment (gaji,
+    /// tipe kerja, benefit, logo, raw_json, ...) dengan NULL. `updated_at` tidak disentuh:
+    /// "terakhir terlihat" sudah dicatat di last_seen_at.
+    pub async fn mark_job_seen(
+        &self,
+        job_id: &str,
+        search_query: Option<&str>,
+        request_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let sql = format!(
+            r#"
+            UPDATE jobs
+               SET search_query = COALESCE(?, search_query),
+                   request_id = COALESCE(?, request_id),
+                   upstream_missing_at = NULL,
+                   last_seen_at = ?,
+                   expired_at = IF({still}, expired_at, NULL),
+                   expired_reason = IF({still}, expired_reason, NULL),
+                   updated_at = updated_at
+             WHERE job_id = ?
+            "#,
+            still = "expired_reason = 'expiration'
+                     AND (job_offer_expiration_datetime_utc < ?
+                          OR (job_offer_expiration_datetime_utc IS NULL AND job_offer_expiration_timestamp < ?))",
+        );
+        let now = Utc::now();
+        sqlx::query(&sql)
+            .bind(search_query)
+            .bind(request_id)
+            .bind(now)
+            .bind(now)
+            .bind(now.timestamp())
+            .bind(now)
+            .bind(now.timestamp())
+            .bind(job_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// job_id yang `updated_at`-nya lebih lama dari `older_than` (paling basi dulu),
+    /// tidak termasuk yang sudah ditandai hilang, yang sudah expired, dan job dengan prefix
+    /// sumber lain.
+    pub async fn stale_job_ids(
+        &self,
+        older_than: DateTime<Utc>,
+        exclude_prefixes: &[&str],
+        limit: i64,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let mut sql = String::from(
+            "SELECT job_id FROM jobs
+              WHERE isdelete = 0 AND upstream_missing_at IS NULL AND expired_at IS NULL AND updated_at < ?",
+        );
+        for _ in exclude_prefixes {
+            sql.push_str(" AND job_id NOT LIKE ?");
+        }
+        sql.push_str(" ORDER BY updated_at ASC LIMIT ?");
+
+        let mut q = sqlx::query_scalar::<_, String>(&sql).bind(older_than);
+        for prefix in exclude_prefixes {
+            q = q.bind(format!("{}%", prefix.replace('_', "\\_")));
+        }
+        q.bind(limit).fetch_all(&self.pool).await
+    }
+
+    /// Tandai job sudah tidak ada di sumber. `updated_at` sengaja tidak disentuh.
+    pub async fn mark_job_missing(&self, job_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE jobs
+               SET upstream_missing_at = COALESCE(upstream_missing_at, ?),
+                   updated_at = updated_at
+             WHERE job_id = ?
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_salary_estimate(
+        &self,
+        title_key: &str,
+        location_key: &str,
+    ) -> Result<Option<SalaryEstimate>, sqlx::Error> {
+        sqlx::query_as::<_, SalaryEstimate>(
+            r#"SELECT * FROM salary_estimates WHERE title_key = ? AND location_key = ? LIMIT 1"#,
+        )
+        .bind(title_key)
+        .bind(location_key)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn upsert_salary_estimate(&self, est: &SalaryEstimate) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO salary_estimates (
+                title_key, location_key, job_title, location, min_salary, max_salary,
+                median_salary, salary_currency, salary_period, publishers, estimates_json, fetched_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                job_title = VALUES(job_title),
+                location = VALUES(location),
+                min_salary = VALUES(min_salary),
+                max_salary = VALUES(max_salary),
+                median_salary = VALUES(median_salary),
+                salary_currency = VALUES(salary_currency),
+                salary_period = VALUES(salary_period),
+                publishers = VALUES(publishers),
+                estimates_json = VALUES(estimates_json),
+                fetched_at = VALUES(fetched_at)
+            "#,
+        )
+        .bind(&est.title_key)
+        .bind(&est.location_key)
+        .bind(&est.job_title)
+        .bind(&est.location)
+        .bind(&est.min_salary)
+        .bind(&est.max_salary)
+        .bind(&est.median_salary)
+        .bind(&est.salary_currency)
+        .bind(&est.salary_period)
+        .bind(&est.publishers)
+        .bind(&est.estimates_json)
+        .bind(est.fetched_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Pasangan (judul, lokasi) dari job tanpa gaji yang belum punya cache estimasi.
+    pub async fn salary_keys_without_estimate(&self, limit: i64) -> Result<Vec<(String, String)>, sqlx::Error> {
+        let sql = format!(
+            r#"
+            SELECT MIN(j.job_title) AS job_title,
+                   MIN(COALESCE(NULLIF(TRIM(j.job_location), ''), j.job_country, '')) AS location
+              FROM jobs j
+              LEFT JOIN salary_estimates s
+                ON s.title_key = {SALARY_TITLE_KEY_SQL}
+               AND s.location_key = {SALARY_LOCATION_KEY_SQL}
+             WHERE j.job_min_salary IS NULL
+               AND j.job_max_salary IS NULL
+               AND TRIM(COALESCE(j.job_title, '')) <> ''
+               AND s.title_key IS NULL
+             GROUP BY {SALARY_TITLE_KEY_SQL}, {SALARY_LOCATION_KEY_SQL}
+             LIMIT ?
+            "#
+        );
+        sqlx::query_as::<_, (String, String)>(&sql)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    // ==================== search_cache ====================
+
+    /// Response search yang masih berlaku (belum lewat `expires_at`).
+    pub async fn get_search_cache(&self, cache_key: &str) -> Result<Option<serde_json::Value>, sqlx::Error> {
+        sqlx::query_scalar::<_, serde_json::Value>(
+            r#"SELECT response_json FROM search_cache WHERE cache_key = ? AND expires_at > ? LIMIT 1"#,
+        )
+        .bind(cache_key)
+        .bind(Utc::now())
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Simpan / timpa entry cache, sekalian buang entry yang sudah kedaluwarsa.
+    pub async fn put_search_cache(
+        &self,
+        cache_key: &str,
+        source: &str,
+        params: &serde_json::Value,
+        response: &serde_json::Value,
+        result_count: usize,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO search_cache (cache_key, source, params_json, response_json, result_count, fetched_at, expires_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                params_json = VALUES(params_json),
+                response_json = VALUES(response_json),
+                result_count = VALUES(result_count),
+                fetched_at = VALUES(fetched_at),
+                expires_at = VALUES(expires_at)
+            "#,
+        )
+        .bind(cache_key)
+        .bind(source)
+        .bind(params)
+        .bind(response)
+        .bind(result_count as i32)
+        .bind(now)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("DELETE FROM search_cache WHERE expires_at <= ?")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     pub async fn find_job(&self, job_id: &str) -> Result<Option<Job>, sqlx::Error> {
         let job = sqlx::query_as::<_, Job>(
             r#"SELECT * FROM jobs WHERE job_id = ? LIMIT 1"#,
@@ -348,7 +1120,8 @@ pub async fn list_jobs_paged(
     pub async fn get_apply_options(&self, job_id: &str) -> Result<Vec<ApplyOption>, sqlx::Error> {
         let options = sqlx::query_as::<_, ApplyOption>(
             r#"
-            SELECT id, job_id, publisher, apply_link, is_direct, created_at
+            SELECT id, job_id, publisher, apply_link, is_direct, created_at,
+                   link_status, link_final_url, link_alive, link_error, link_checked_at
               FROM job_apply_options
              WHERE job_id = ?
              ORDER BY id ASC
@@ -416,4 +1189,341 @@ pub async fn list_jobs_paged(
         .await?;
         Ok(())
     }
+
+    // ==================== api_calls (kuota RapidAPI) ====================
+
+    pub async fn insert_api_call(&self, call: &ApiCall) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO api_calls
+                (host, endpoint, status_code, latency_ms, requests_remaining, ratelimit_json, error, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, UTC_TIMESTAMP())
+            "#,
+        )
+        .bind(&call.host)
+        .bind(&call.endpoint)
+        .bind(call.status_code)
+        .bind(call.latency_ms)
+        .bind(call.requests_remaining)
+        .bind(&call.ratelimit_json)
+        .bind(&call.error)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Jumlah call ke `host` sejak awal hari ini (UTC). Batas hari dihitung di Rust, bukan
+    /// `UTC_DATE()`, supaya tidak bergantung pada time zone server/sesi.
+    pub async fn count_api_calls_today(&self, host: &str) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM api_calls WHERE host = ? AND created_at >= ?"#)
+            .bind(host)
+            .bind(utc_day_start(0))
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    pub async fn usage_per_day(&self, days: i64) -> Result<Vec<UsageDay>, sqlx::Error> {
+        sqlx::query_as::<_, UsageDay>(
+            r#"
+            SELECT DATE(created_at) AS day,
+                   host,
+                   COUNT(*) AS calls,
+                   CAST(SUM(status_code IS NULL OR status_code >= 400) AS SIGNED) AS errors
+              FROM api_calls
+             WHERE created_at >= ?
+             GROUP BY DATE(created_at), host
+             ORDER BY day DESC, host ASC
+            "#,
+        )
+        .bind(utc_day_start(days))
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Header rate-limit terbaru per host (dari call terakhir yang membawa header tsb).
+    pub async fn latest_quota_per_host(&self) -> Result<Vec<HostQuota>, sqlx::Error> {
+        sqlx::query_as::<_, HostQuota>(
+            r#"
+            SELECT c.host, c.requests_remaining, c.ratelimit_json, c.created_at
+              FROM api_calls c
+              JOIN (SELECT host, MAX(id) AS id
+                      FROM api_calls
+                     WHERE ratelimit_json IS NOT NULL
+                     GROUP BY host) last ON last.id = c.id
+             ORDER BY c.host ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    // ==================== saved_searches (scheduler) ====================
+
+    pub async fn insert_saved_search(
+        &self,
+        source: &str,
+        req: &SearchRequest,
+        interval_minutes: i32,
+    ) -> Result<u64, sqlx::Error> {
+        let res = sqlx::query(
+            r#"
+            INSERT INTO saved_searches
+                (source, query, country, language, date_posted, num_pages, location,
+                 experience_levels, workplace_types, employment_types, interval_minutes)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(source)
+        .bind(&req.query)
+        .bind(&req.country)
+        .bind(&req.language)
+        .bind(&req.date_posted)
+        .bind(req.num_pages.max(1))
+        .bind(&req.location)
+        .bind(&req.experience_levels)
+        .bind(&req.workplace_types)
+        .bind(&req.employment_types)
+        .bind(interval_minutes.max(1))
+        .execute(&self.pool)
+        .await?;
+        Ok(res.last_insert_id())
+    }
+
+    pub async fn list_saved_searches(&self) -> Result<Vec<SavedSearch>, sqlx::Error> {
+        sqlx::query_as::<_, SavedSearch>(r#"SELECT * FROM saved_searches ORDER BY id DESC"#)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Saved search aktif yang sudah lewat intervalnya (atau belum pernah jalan).
+    pub async fn due_saved_searches(&self) -> Result<Vec<SavedSearch>, sqlx::Error> {
+        sqlx::query_as::<_, SavedSearch>(
+            r#"
+            SELECT * FROM saved_searches
+             WHERE is_active = 1
+               AND (last_run_at IS NULL
+                    OR last_run_at + INTERVAL interval_minutes MINUTE <= UTC_TIMESTAMP())
+             ORDER BY last_run_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn delete_saved_search(&self, id: u64) -> Result<(), sqlx::Error> {
+        sqlx::query(r#"DELETE FROM saved_searches WHERE id = ?"#)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // ==================== search_runs ====================
+
+    /// Simpan satu run + semua job yang tersimpan pada run itu (ditandai baru / update).
+    pub async fn record_search_run(
+        &self,
+        run: &SearchRun,
+        job_ids: &[String],
+        new_job_ids: &[String],
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let run_id = sqlx::query(
+            r#"
+            INSERT INTO search_runs (
+                source, origin, saved_search_id, query, params_json, started_at, finished_at,
+                pages, result_count, new_count, updated_count, cached_pages, error
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&run.source)
+        .bind(&run.origin)
+        .bind(run.saved_search_id)
+        .bind(&run.query)
+        .bind(&run.params_json)
+        .bind(run.started_at)
+        .bind(run.finished_at)
+        .bind(run.pages)
+        .bind(run.result_count)
+        .bind(run.new_count)
+        .bind(run.updated_count)
+        .bind(run.cached_pages)
+        .bind(&run.error)
+        .execute(&mut *tx)
+        .await?
+        .last_insert_id();
+
+        for job_id in job_ids {
+            sqlx::query(r#"INSERT IGNORE INTO search_run_jobs (run_id, job_id, is_new) VALUES (?, ?, ?)"#)
+                .bind(run_id)
+                .bind(job_id)
+                .bind(new_job_ids.contains(job_id))
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        // run scheduler: geser jadwal saved search-nya
+        if let Some(saved_search_id) = run.saved_search_id {
+            sqlx::query(r#"UPDATE saved_searches SET last_run_at = UTC_TIMESTAMP() WHERE id = ?"#)
+                .bind(saved_search_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(run_id)
+    }
+
+    /// Run saved search terbaru (search_runs dengan saved_search_id), untuk /saved.
+    pub async fn recent_saved_search_runs(&self, limit: i64) -> Result<Vec<SearchRun>, sqlx::Error> {
+        sqlx::query_as::<_, SearchRun>(
+            r#"SELECT * FROM search_runs WHERE saved_search_id IS NOT NULL ORDER BY id DESC LIMIT ?"#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Job yang baru pertama kali terlihat pada run saved search mulai `min_run_id`.
+    pub async fn new_jobs_since_run(&self, min_run_id: u64) -> Result<Vec<RunNewJob>, sqlx::Error> {
+        sqlx::query_as::<_, RunNewJob>(
+            r#"
+            SELECT n.run_id, n.job_id, j.job_title
+              FROM search_run_jobs n
+              JOIN search_runs r ON r.id = n.run_id
+              LEFT JOIN jobs j ON j.job_id = n.job_id
+             WHERE n.run_id >= ? AND n.is_new = 1 AND r.saved_search_id IS NOT NULL
+             ORDER BY n.run_id DESC, n.job_id ASC
+            "#,
+        )
+        .bind(min_run_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Run terbaru, opsional difilter per sumber.
+    pub async fn list_search_runs(
+        &self,
+        source: Option<&str>,
+        page: usize,
+        per_page: usize,
+    ) -> Result<(Vec<SearchRun>, i64), sqlx::Error> {
+        let offset = (page.max(1) - 1) * per_page;
+        let source = source.map(str::trim).filter(|s| !s.is_empty());
+
+        let total: i64 = sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM search_runs WHERE (? IS NULL OR source = ?)"#,
+        )
+        .bind(source)
+        .bind(source)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let runs = sqlx::query_as::<_, SearchRun>(
+            r#"
+            SELECT * FROM search_runs
+             WHERE (? IS NULL OR source = ?)
+             ORDER BY id DESC
+             LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(source)
+        .bind(source)
+        .bind(per_page as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok((runs, total))
+    }
+
+    pub async fn find_search_run(&self, id: u64) -> Result<Option<SearchRun>, sqlx::Error> {
+        sqlx::query_as::<_, SearchRun>(r#"SELECT * FROM search_runs WHERE id = ? LIMIT 1"#)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Job hasil satu run; job baru di atas.
+    pub async fn search_run_jobs(&self, run_id: u64) -> Result<Vec<SearchRunJob>, sqlx::Error> {
+        sqlx::query_as::<_, SearchRunJob>(
+            r#"
+            SELECT r.job_id, r.is_new, j.job_title, j.employer_name, j.job_location
+              FROM search_run_jobs r
+              LEFT JOIN jobs j ON j.job_id = r.job_id
+             WHERE r.run_id = ?
+             ORDER BY r.is_new DESC, j.job_title ASC
+            "#,
+        )
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Sumber yang pernah tercatat (untuk filter di /runs).
+    pub async fn search_run_sources(&self) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar::<_, String>(r#"SELECT DISTINCT source FROM search_runs ORDER BY source"#)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    // ==================== search_cursors (resume paging) ====================
+
+    pub async fn get_search_cursor(&self, source: &str, query_key: &str) -> Result<Option<String>, sqlx::Error> {
+        let cursor = sqlx::query_scalar::<_, Option<String>>(
+            r#"SELECT next_cursor FROM search_cursors WHERE source = ? AND query_key = ? LIMIT 1"#,
+        )
+        .bind(source)
+        .bind(query_key)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(cursor.flatten())
+    }
+
+    /// Simpan cursor terakhir untuk query ini; `None` = hasil sudah habis.
+    pub async fn save_search_cursor(
+        &self,
+        source: &str,
+        query_key: &str,
+        next_cursor: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO search_cursors (source, query_key, next_cursor, updated_at)
+            VALUES (?, ?, ?, UTC_TIMESTAMP())
+            ON DUPLICATE KEY UPDATE
+                next_cursor = VALUES(next_cursor),
+                updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(source)
+        .bind(query_key)
+        .bind(next_cursor)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_shows_duplicates_of_expired_canonical_jobs() {
+        let (sql, binds) = ListFilter::default().where_sql();
+        assert!(sql.contains(LISTED_JOB_SQL), "{sql}");
+        assert!(sql.contains("c.expired_at IS NULL"), "{sql}");
+        assert!(sql.contains("j.expired_at IS NULL"), "{sql}");
+        assert!(binds.is_empty());
+    }
+
+    #[test]
+    fn include_expired_keeps_duplicates_hidden() {
+        let filter = ListFilter { include_expired: true, ..Default::default() };
+        let (sql, _) = filter.where_sql();
+        assert!(sql.contains(LISTED_JOB_INCLUDING_EXPIRED_SQL), "{sql}");
+        assert!(!sql.contains("expired_at IS NULL"), "{sql}");
+    }
 }
@@ -0,0 +1,263 @@
+// src/dedup.rs
+//
+// Deteksi duplikat lintas sumber. Posting yang sama sering masuk dua kali, misalnya
+// sebagai job_id JSearch dan `li_` LinkedIn (atau lewat feed/ATS). Setiap kali job
+// disimpan, dihitung fingerprint ternormalisasi dari perusahaan, judul dan kota/negara.
+// Job dari sumber lain (prefix job_id berbeda) dengan fingerprint sama dan deskripsi
+// mirip (Jaccard shingle 3 kata) ditautkan ke satu job kanonik lewat
+// `jobs.canonical_job_id`. Opsi apply job duplikat disalin ke baris kanonik (ditandai
+// `copied_from_job_id`, dihapus lagi jika tautannya lepas), dan /list hanya menampilkan
+// job kanonik.
+//
+// `DEDUP_MIN_SIMILARITY` (default 0.5; 0 = fingerprint sama sudah cukup).
+// Selain mode 0, kedua deskripsi harus cukup panjang untuk dinilai; tanpa skor tidak ditautkan.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::env;
+use std::hash::{Hash, Hasher};
+
+use sha2::{Digest, Sha256};
+
+use crate::database::Database;
+use crate::job_source;
+use crate::models::Job;
+
+/// Akhiran badan hukum yang diabaikan saat membandingkan nama perusahaan.
+const LEGAL_SUFFIXES: &[&str] = &[
+    "inc", "llc", "ltd", "limited", "corp", "corporation", "co", "company", "gmbh", "bv", "sa",
+    "ag", "plc", "pt", "tbk", "persero",
+];
+
+/// Kata di judul yang sering ditambahkan satu sumber tapi tidak di sumber lain.
+const TITLE_NOISE: &[&str] = &["remote", "hybrid", "onsite", "urgent", "hiring"];
+
+/// Jumlah kata per shingle deskripsi.
+const SHINGLE_WORDS: usize = 3;
+
+pub fn min_similarity_from_env() -> f64 {
+    env::var("DEDUP_MIN_SIMILARITY")
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .map(|v| v.clamp(0.0, 1.0))
+        .unwrap_or(0.5)
+}
+
+/// Huruf kecil, selain huruf/angka jadi spasi, spasi dirapatkan.
+fn normalize_text(s: &str) -> String {
+    s.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_employer(s: &str) -> String {
+    normalize_text(s)
+        .split(' ')
+        .filter(|w| !LEGAL_SUFFIXES.contains(w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Judul tanpa isi kurung ("(m/w/d)", "[Remote]") dan kata noise.
+fn normalize_title(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    normalize_text(&out)
+        .split(' ')
+        .filter(|w| !TITLE_NOISE.contains(w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Kota (`job_city`, fallback segmen pertama `job_location`), selain itu negara.
+fn location_key(job: &Job) -> String {
+    let city = job
+        .job_city
+        .as_deref()
+        .filter(|c| !c.trim().is_empty())
+        .or_else(|| job.job_location.as_deref().and_then(|l| l.split(',').next()))
+        .map(normalize_text)
+        .filter(|c| !c.is_empty());
+    city.or_else(|| job.job_country.as_deref().map(normalize_text))
+        .unwrap_or_default()
+}
+
+/// 32 hex sha256 dari perusahaan|judul|lokasi ternormalisasi; `None` jika perusahaan
+/// atau judul kosong (tidak cukup informasi untuk menyatakan duplikat).
+pub fn fingerprint(job: &Job) -> Option<String> {
+    let employer = normalize_employer(job.employer_name.as_deref()?);
+    let title = normalize_title(job.job_title.as_deref()?);
+    if employer.is_empty() || title.is_empty() {
+        return None;
+    }
+    let digest = Sha256::digest(format!("{employer}|{title}|{}", location_key(job)).as_bytes());
+    Some(digest.iter().take(16).map(|b| format!("{b:02x}")).collect())
+}
+
+fn shingles(text: &str) -> HashSet<u64> {
+    let norm = normalize_text(text);
+    let words: Vec<&str> = norm.split(' ').filter(|w| !w.is_empty()).collect();
+    words
+        .windows(SHINGLE_WORDS)
+        .map(|w| {
+            let mut h = DefaultHasher::new();
+            w.hash(&mut h);
+            h.finish()
+        })
+        .collect()
+}
+
+/// Jaccard shingle dua deskripsi; `None` jika salah satu terlalu pendek untuk dibandingkan.
+pub fn similarity(a: &str, b: &str) -> Option<f64> {
+    let (a, b) = (shingles(a), shingles(b));
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    let inter = a.intersection(&b).count();
+    let union = a.len() + b.len() - inter;
+    Some(inter as f64 / union as f64)
+}
+
+fn is_duplicate(a: Option<&str>, b: Option<&str>, min: f64) -> bool {
+    if min <= 0.0 {
+        return true;
+    }
+    match (a, b) {
+        (Some(a), Some(b)) => similarity(a, b).is_some_and(|s| s >= min),
+        _ => false,
+    }
+}
+
+/// Hitung fingerprint `job` (yang baru disimpan), cari job kanonik dari sumber lain dengan
+/// fingerprint sama dan deskripsi mirip, lalu tautkan + gabungkan opsi apply. Opsi yang dulu
+/// disalin dari job ini dibuang dulu, jadi tautan yang lepas tidak meninggalkan link.
+/// Tanpa deskripsi masuk, deskripsi tersimpan yang dibandingkan. Return id kanonik jika duplikat.
+pub async fn link_job(db: &Database, job: &Job, min_similarity: f64) -> Result<Option<String>, sqlx::Error> {
+    db.remove_copied_apply_options(&job.job_id).await?;
+    let Some(fp) = fingerprint(job) else {
+        db.set_job_dedup(&job.job_id, None, None).await?;
+        return Ok(None);
+    };
+
+    let source = job_source::source_prefix(&job.job_id);
+    let candidates = db.dedup_candidates(&fp, &job.job_id).await?;
+    // upsert tanpa deskripsi (baris CSV, entri feed tanpa ringkasan) tetap menyimpan yang lama
+    let desc = match job.job_description.clone().filter(|d| !d.trim().is_empty()) {
+        Some(d) => Some(d),
+        None => db.stored_job_description(&job.job_id).await?.filter(|d| !d.trim().is_empty()),
+    };
+    let canonical = candidates
+        .into_iter()
+        .filter(|(id, _)| job_source::source_prefix(id) != source)
+        .find(|(_, other)| is_duplicate(desc.as_deref(), other.as_deref(), min_similarity))
+        .map(|(id, _)| id);
+
+    db.set_job_dedup(&job.job_id, Some(&fp), canonical.as_deref()).await?;
+    if let Some(canonical) = &canonical {
+        db.merge_duplicate_into(&job.job_id, canonical).await?;
+    }
+    Ok(canonical)
+}
+
+#[derive(Debug, Default)]
+pub struct RebuildSummary {
+    pub checked: usize,
+    pub duplicates: usize,
+}
+
+/// Hitung ulang semua fingerprint dari nol (job terlama jadi kanonik). Untuk database
+/// yang sudah berisi job sebelum kolom dedup ada, atau setelah `DEDUP_MIN_SIMILARITY` diubah.
+pub async fn rebuild(db: &Database) -> Result<RebuildSummary, sqlx::Error> {
+    let min = min_similarity_from_env();
+    db.reset_job_dedup().await?;
+
+    let mut summary = RebuildSummary::default();
+    for job_id in db.job_ids_oldest_first().await? {
+        let Some(job) = db.find_job(&job_id).await? else { continue };
+        summary.checked += 1;
+        if link_job(db, &job, min).await?.is_some() {
+            summary.duplicates += 1;
+        }
+    }
+    Ok(summary)
+}
+
+/// `job_finder dedup`: rebuild lalu cetak ringkasan. Mengembalikan exit code.
+pub async fn run_cli(db: &Database) -> i32 {
+    match rebuild(db).await {
+        Ok(s) => {
+            println!("dedup: {} job dicek, {} duplikat ditautkan", s.checked, s.duplicates);
+            0
+        }
+        Err(e) => {
+            eprintln!("dedup error: {e}");
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESC: &str = "We are looking for a Senior Rust Engineer to build backend services with tokio and sqlx";
+
+    fn job(id: &str, employer: &str, title: &str, city: &str) -> Job {
+        Job {
+            job_id: id.into(),
+            employer_name: Some(employer.into()),
+            job_title: Some(title.into()),
+            job_city: Some(city.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_legal_suffix_brackets_and_noise() {
+        let a = job("a", "Example Labs GmbH", "Senior Rust Engineer (m/w/d)", "Berlin");
+        let b = job("li_b", "example labs", "Senior Rust Engineer - Remote", "berlin");
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+        assert_ne!(fingerprint(&a), fingerprint(&job("c", "Example Labs", "Senior Rust Engineer", "Munich")));
+        assert_eq!(fingerprint(&job("d", "  ", "Engineer", "Berlin")), None);
+    }
+
+    #[test]
+    fn duplicate_needs_a_real_similarity_score() {
+        assert!(is_duplicate(Some(DESC), Some(DESC), 0.5));
+        assert!(!is_duplicate(Some(DESC), Some("Completely different text about hiring a nurse in a hospital"), 0.5));
+        // tanpa deskripsi / terlalu pendek -> tidak ada skor -> bukan duplikat
+        assert!(!is_duplicate(None, Some(DESC), 0.5));
+        assert!(!is_duplicate(Some(DESC), None, 0.5));
+        assert!(!is_duplicate(Some("Rust"), Some(DESC), 0.5));
+        // DEDUP_MIN_SIMILARITY=0: fingerprint sama sudah cukup
+        assert!(is_duplicate(None, None, 0.0));
+    }
+
+    #[test]
+    fn similarity_is_jaccard_over_shingles() {
+        assert_eq!(similarity(DESC, DESC), Some(1.0));
+        assert_eq!(similarity("a b", DESC), None);
+        let s = similarity(DESC, &format!("{DESC} and warp")).unwrap();
+        assert!(s > 0.8 && s < 1.0, "{s}");
+    }
+
+    #[test]
+    fn source_prefix_separates_sources() {
+        assert_eq!(job_source::source_prefix("li_4012345678"), "li_");
+        assert_eq!(job_source::source_prefix("feed_abc"), "feed_");
+        assert_eq!(job_source::source_prefix("mockJS001=="), "");
+        assert_ne!(job_source::source_prefix("gh_1"), job_source::source_prefix("lever_1"));
+    }
+}
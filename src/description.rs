@@ -0,0 +1,323 @@
+// src/description.rs
+//
+// Normalisasi deskripsi job sebelum disimpan. Sumber mengirim format berbeda-beda:
+// LinkedIn / ATS / feed berupa HTML (kadang di-escape), JSearch berupa teks polos
+// dengan bullet "•" dan baris baru. Semuanya diubah ke Markdown bersih, lalu disimpan
+// di `job_description`; teks asli disimpan di `job_description_raw`. Halaman detail
+// me-render Markdown tersebut ke HTML yang sudah disanitasi (ammonia).
+//
+// Yang dibuang saat normalisasi: gambar (tracking pixel), parameter `utm_*` / tracking
+// di link, karakter zero-width, hashtag LinkedIn (`#LI-...`), dan baris boilerplate
+// seperti "Show more" / "Apply now".
+
+use std::borrow::Cow;
+
+use pulldown_cmark::{html, Options, Parser};
+
+use crate::models::Job;
+
+/// Baris (setelah trim, tanpa penanda bold/heading, huruf kecil) yang dibuang jika sama persis.
+const BOILERPLATE_LINES: &[&str] = &[
+    "show more",
+    "show less",
+    "see more",
+    "see less",
+    "apply now",
+    "click here to apply",
+    "apply for this job",
+    "see this and similar jobs on linkedin",
+    "report this job",
+];
+
+/// Parameter query link yang dibuang.
+const TRACKING_PARAMS: &[&str] = &["utm_", "gclid", "fbclid", "trk", "refid", "trackingid", "mc_cid", "mc_eid"];
+
+const BULLETS: &[char] = &['•', '·', '●', '▪', '◦', '‣', '–', '*', '-'];
+
+/// Salinan `job` dengan deskripsi ternormalisasi; teks asli dipindah ke `job_description_raw`.
+/// `job_description` dari sumber selalu dipakai jika ada (raw lama bisa sudah basi);
+/// raw hanya jadi fallback untuk job tanpa deskripsi.
+pub fn normalized(job: &Job) -> Job {
+    let mut job = job.clone();
+    let fresh = job.job_description.take().filter(|d| !d.trim().is_empty());
+    let raw = fresh.or_else(|| job.job_description_raw.take());
+    job.job_description = raw.as_deref().map(normalize).filter(|d| !d.is_empty());
+    job.job_description_raw = raw;
+    job
+}
+
+/// Deskripsi mentah (HTML atau teks) -> Markdown.
+pub fn normalize(raw: &str) -> String {
+    let text = clean_chars(raw);
+    let text = if looks_like_escaped_html(&text) { Cow::Owned(unescape_html(&text)) } else { Cow::Borrowed(&*text) };
+
+    let markdown = if looks_like_html(&text) {
+        // entity (&nbsp; dll.) baru di-decode oleh parser HTML
+        clean_chars(&html2md::parse_html(&text))
+    } else {
+        plain_to_markdown(&text)
+    };
+    tidy_markdown(&markdown)
+}
+
+/// Markdown -> HTML aman untuk halaman detail.
+pub fn to_safe_html(markdown: &str) -> String {
+    let mut opts = Options::empty();
+    opts.insert(Options::ENABLE_TABLES);
+    opts.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let mut out = String::with_capacity(markdown.len() * 2);
+    html::push_html(&mut out, Parser::new_ext(markdown, opts));
+    ammonia::Builder::default()
+        .rm_tags(&["img"])
+        .clean(&out)
+        .to_string()
+}
+
+/// HTML siap tampil untuk `job`. Baris lama (sebelum normalisasi ada, `job_description_raw`
+/// masih NULL) dinormalisasi saat itu juga tanpa menulis ke DB.
+pub fn detail_html(job: &Job) -> Option<String> {
+    let desc = job.job_description.as_deref().filter(|d| !d.trim().is_empty())?;
+    let markdown = if job.job_description_raw.is_some() { Cow::Borrowed(desc) } else { Cow::Owned(normalize(desc)) };
+    Some(to_safe_html(&markdown))
+}
+
+fn clean_chars(s: &str) -> String {
+    s.chars()
+        .filter(|c| !matches!(c, '\u{200b}' | '\u{200c}' | '\u{200d}' | '\u{feff}' | '\r'))
+        .map(|c| if c == '\u{a0}' { ' ' } else { c })
+        .collect()
+}
+
+fn looks_like_escaped_html(s: &str) -> bool {
+    let lower = s.to_lowercase();
+    ["&lt;p&gt;", "&lt;br", "&lt;li&gt;", "&lt;div", "&lt;ul&gt;", "&lt;strong&gt;"]
+        .iter()
+        .any(|t| lower.contains(t))
+}
+
+fn looks_like_html(s: &str) -> bool {
+    let lower = s.to_lowercase();
+    ["<p>", "<p ", "<br", "<li>", "<li ", "<ul", "<ol", "<div", "<strong>", "<b>", "<h1", "<h2", "<h3", "<span"]
+        .iter()
+        .any(|t| lower.contains(t))
+}
+
+fn unescape_html(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Teks polos ala JSearch: baris berawalan bullet jadi list Markdown, baris lain jadi
+/// paragraf sendiri (baris baru tunggal tidak hilang saat di-render).
+fn plain_to_markdown(s: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut prev_bullet = false;
+    for line in s.lines() {
+        let t = line.trim();
+        if t.is_empty() {
+            prev_bullet = false;
+            continue;
+        }
+        let item = t
+            .strip_prefix(BULLETS)
+            .filter(|rest| rest.starts_with(' ') || rest.starts_with('\t') || !t.starts_with(['*', '-']))
+            .map(str::trim);
+        match item {
+            Some(item) if !item.is_empty() => {
+                if !prev_bullet && !out.is_empty() {
+                    out.push(String::new());
+                }
+                out.push(format!("- {}", escape_markdown_line(item)));
+                prev_bullet = true;
+            }
+            _ => {
+                if !out.is_empty() {
+                    out.push(String::new());
+                }
+                out.push(escape_markdown_line(t));
+                prev_bullet = false;
+            }
+        }
+    }
+    out.join("\n")
+}
+
+/// Cegah teks polos terbaca sebagai heading / list bernomor / blok kode Markdown.
+fn escape_markdown_line(s: &str) -> String {
+    let mut line = s.to_string();
+    if line.starts_with('#') || line.starts_with('>') || line.starts_with('=') {
+        line.insert(0, '\\');
+    }
+    if let Some(pos) = line.find(". ") {
+        if pos > 0 && line[..pos].chars().all(|c| c.is_ascii_digit()) {
+            line.insert(pos, '\\');
+        }
+    }
+    line
+}
+
+/// Buang gambar, tracking, hashtag LinkedIn dan boilerplate; rapikan baris kosong.
+fn tidy_markdown(md: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut blank = 0;
+    for line in md.lines() {
+        let line = strip_images(line);
+        let line = strip_tracking_params(&line);
+        let line = strip_li_hashtags(&line);
+        let t = line.trim_end();
+
+        let plain = t.trim().trim_matches(|c: char| c == '*' || c == '_' || c == '#').trim().to_lowercase();
+        if BOILERPLATE_LINES.contains(&plain.as_str()) {
+            continue;
+        }
+        if t.trim().is_empty() {
+            blank += 1;
+            if blank > 1 || out.is_empty() {
+                continue;
+            }
+        } else {
+            blank = 0;
+        }
+        out.push(t.to_string());
+    }
+    while out.last().map(|l| l.trim().is_empty()).unwrap_or(false) {
+        out.pop();
+    }
+    out.join("\n")
+}
+
+/// `![alt](url)` dihapus (tracking pixel / logo).
+fn strip_images(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("![") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start..];
+        match after.find("](").and_then(|mid| after[mid..].find(')').map(|end| mid + end + 1)) {
+            Some(end) => rest = &after[end..],
+            None => {
+                out.push_str(after);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Hapus parameter tracking dari setiap URL http(s) di baris.
+fn strip_tracking_params(line: &str) -> String {
+    if !line.contains("http") || !line.contains('?') {
+        return line.to_string();
+    }
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("http") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let end = after
+            .find(|c: char| c.is_whitespace() || matches!(c, ')' | '>' | '"' | '\'' | ']'))
+            .unwrap_or(after.len());
+        out.push_str(&clean_url(&after[..end]));
+        rest = &after[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn clean_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else { return url.to_string() };
+    let (query, fragment) = match query.split_once('#') {
+        Some((q, f)) => (q, Some(f)),
+        None => (query, None),
+    };
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|p| {
+            let key = p.split('=').next().unwrap_or("").to_lowercase();
+            !p.is_empty() && !TRACKING_PARAMS.iter().any(|t| key.starts_with(t))
+        })
+        .collect();
+    let mut out = base.to_string();
+    if !kept.is_empty() {
+        out.push('?');
+        out.push_str(&kept.join("&"));
+    }
+    if let Some(f) = fragment {
+        out.push('#');
+        out.push_str(f);
+    }
+    out
+}
+
+/// Kode tracking rekruter LinkedIn: "#LI-Remote", "#LI-DNI", "#LI-JS1".
+fn strip_li_hashtags(line: &str) -> String {
+    if !line.contains("#LI-") && !line.contains("#li-") {
+        return line.to_string();
+    }
+    line.split(' ')
+        .filter(|w| !w.trim_start_matches(['\\', '*', '_']).to_uppercase().starts_with("#LI-"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn html_becomes_markdown() {
+        let md = normalize("<p>We build <strong>Rust</strong> services.</p><ul><li>tokio</li><li>sqlx</li></ul>");
+        assert!(md.contains("We build **Rust** services."), "{md}");
+        assert!(md.contains("* tokio") || md.contains("- tokio"), "{md}");
+        assert!(!md.contains('<'), "{md}");
+    }
+
+    #[test]
+    fn escaped_html_is_unescaped_first() {
+        let md = normalize("&lt;p&gt;Hello &amp;amp; welcome&lt;/p&gt;&lt;ul&gt;&lt;li&gt;Remote&lt;/li&gt;&lt;/ul&gt;");
+        assert!(md.contains("Hello & welcome"), "{md}");
+        assert!(md.contains("Remote"), "{md}");
+        assert!(!md.contains("&lt;") && !md.contains("<p>"), "{md}");
+    }
+
+    #[test]
+    fn jsearch_bullets_become_a_list() {
+        let md = normalize("Responsibilities:\n• Build APIs\n• Review code\n\n1. Not a list");
+        assert_eq!(md, "Responsibilities:\n\n- Build APIs\n- Review code\n\n1\\. Not a list");
+    }
+
+    #[test]
+    fn tracking_params_are_stripped() {
+        let md = normalize("Apply at https://example.com/jobs/1?utm_source=linkedin&ref=abc&gclid=x#apply");
+        assert_eq!(md, "Apply at https://example.com/jobs/1?ref=abc#apply");
+        assert_eq!(clean_url("https://example.com/?utm_medium=a&trk=b"), "https://example.com/");
+    }
+
+    #[test]
+    fn linkedin_hashtags_and_boilerplate_are_dropped() {
+        let md = normalize("Great team. #LI-Remote #LI-DNI\nShow more\nApply now");
+        assert_eq!(md, "Great team.");
+    }
+
+    #[test]
+    fn normalized_prefers_fresh_description_over_stale_raw() {
+        let job = Job {
+            job_description: Some("<p>New text</p>".into()),
+            job_description_raw: Some("<p>Old text</p>".into()),
+            ..Default::default()
+        };
+        let out = normalized(&job);
+        assert_eq!(out.job_description.as_deref(), Some("New text"));
+        assert_eq!(out.job_description_raw.as_deref(), Some("<p>New text</p>"));
+
+        let only_raw = Job { job_description_raw: Some("<p>Old text</p>".into()), ..Default::default() };
+        assert_eq!(normalized(&only_raw).job_description.as_deref(), Some("Old text"));
+        assert_eq!(normalized(&Job::default()).job_description, None);
+    }
+}
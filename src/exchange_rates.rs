@@ -0,0 +1,147 @@
+// src/exchange_rates.rs
+//
+// Tabel kurs untuk normalisasi gaji (lihat `salary::annualize`). Dibaca sekali dari file
+// JSON lokal (`EXCHANGE_RATES_FILE`, default `exchange_rates.json`) dengan format yang sama
+// seperti API kurs umum:
+//
+//   { "base": "USD", "date": "2026-10-01", "rates": { "EUR": 0.92, "IDR": 16300, ... } }
+//
+// artinya 1 `base` = N mata uang tersebut. Mata uang tujuan (`SALARY_BASE_CURRENCY`,
+// default USD) tidak harus sama dengan `base` file; konversi dihitung silang.
+// File berubah -> restart lalu `cargo run -- salary-normalize`.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct RatesFile {
+    base: String,
+    #[serde(default)]
+    date: Option<String>,
+    rates: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExchangeRates {
+    /// mata uang hasil normalisasi
+    pub target: String,
+    /// tanggal kurs di file (informasi saja)
+    pub date: Option<String>,
+    /// 1 mata uang referensi file = N mata uang (kode huruf besar); referensi sendiri = 1
+    rates: HashMap<String, f64>,
+}
+
+fn currency_code(s: &str) -> String {
+    s.trim().to_uppercase()
+}
+
+impl ExchangeRates {
+    /// Tabel kosong: hanya `target` sendiri yang bisa "dikonversi".
+    pub fn empty(target: &str) -> Self {
+        let target = currency_code(target);
+        let rates = HashMap::from([(target.clone(), 1.0)]);
+        ExchangeRates { target, date: None, rates }
+    }
+
+    pub fn from_json(text: &str, target: &str) -> Result<Self, String> {
+        let file: RatesFile = serde_json::from_str(text).map_err(|e| format!("JSON kurs tidak valid: {e}"))?;
+        let mut rates: HashMap<String, f64> = file
+            .rates
+            .into_iter()
+            .filter(|(_, v)| v.is_finite() && *v > 0.0)
+            .map(|(k, v)| (currency_code(&k), v))
+            .collect();
+        rates.insert(currency_code(&file.base), 1.0);
+
+        let target = currency_code(target);
+        if !rates.contains_key(&target) {
+            return Err(format!("mata uang {target} tidak ada di file kurs"));
+        }
+        Ok(ExchangeRates { target, date: file.date, rates })
+    }
+
+    /// `EXCHANGE_RATES_FILE` + `SALARY_BASE_CURRENCY`. File tidak ada / rusak -> tabel kosong
+    /// (hanya gaji dalam mata uang tujuan yang dinormalisasi) + pesan di stderr.
+    pub fn from_env() -> Self {
+        let path = env::var("EXCHANGE_RATES_FILE").unwrap_or_else(|_| "exchange_rates.json".to_string());
+        let target = env::var("SALARY_BASE_CURRENCY")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| "USD".to_string());
+
+        let loaded = std::fs::read_to_string(&path)
+            .map_err(|e| format!("{path}: {e}"))
+            .and_then(|text| ExchangeRates::from_json(&text, &target).map_err(|e| format!("{path}: {e}")));
+        match loaded {
+            Ok(rates) => rates,
+            Err(e) => {
+                eprintln!("exchange rates: {e}; hanya gaji {} yang dinormalisasi", currency_code(&target));
+                ExchangeRates::empty(&target)
+            }
+        }
+    }
+
+    /// `amount` dalam `currency` -> mata uang `target`; `None` jika kurs tidak diketahui.
+    pub fn convert(&self, amount: f64, currency: &str) -> Option<f64> {
+        let from = self.rates.get(&currency_code(currency))?;
+        let to = self.rates.get(&self.target)?;
+        Some(amount / from * to)
+    }
+}
+
+/// Tabel kurs proses ini (dimuat sekali dari env saat pertama dipakai).
+pub fn global() -> &'static ExchangeRates {
+    static RATES: OnceLock<ExchangeRates> = OnceLock::new();
+    RATES.get_or_init(ExchangeRates::from_env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"{ "base": "usd", "date": "2026-10-01",
+        "rates": { "EUR": 0.5, "idr": 16000, "BAD": 0, "NAN": -3 } }"#;
+
+    fn approx(a: Option<f64>, b: f64) -> bool {
+        a.is_some_and(|a| (a - b).abs() < 1e-6)
+    }
+
+    #[test]
+    fn from_json_reads_rates() {
+        let rates = ExchangeRates::from_json(FILE, "usd").unwrap();
+        assert_eq!(rates.target, "USD");
+        assert_eq!(rates.date.as_deref(), Some("2026-10-01"));
+        assert!(approx(rates.convert(100.0, "usd"), 100.0));
+        assert!(approx(rates.convert(16000.0, "IDR"), 1.0));
+        assert!(approx(rates.convert(1.0, " eur "), 2.0));
+        // kurs 0 / negatif dibuang, mata uang tak dikenal -> None
+        assert_eq!(rates.convert(1.0, "BAD"), None);
+        assert_eq!(rates.convert(1.0, "NAN"), None);
+        assert_eq!(rates.convert(1.0, "JPY"), None);
+    }
+
+    #[test]
+    fn converts_across_rates_when_target_is_not_base() {
+        let rates = ExchangeRates::from_json(FILE, "EUR").unwrap();
+        assert!(approx(rates.convert(16000.0, "IDR"), 0.5));
+        assert!(approx(rates.convert(10.0, "USD"), 5.0));
+        assert!(approx(rates.convert(3.0, "EUR"), 3.0));
+    }
+
+    #[test]
+    fn rejects_bad_files() {
+        assert!(ExchangeRates::from_json(FILE, "JPY").is_err());
+        assert!(ExchangeRates::from_json("{}", "USD").is_err());
+        assert!(ExchangeRates::from_json("not json", "USD").is_err());
+    }
+
+    #[test]
+    fn empty_table_only_knows_target() {
+        let rates = ExchangeRates::empty("usd");
+        assert!(approx(rates.convert(5.0, "USD"), 5.0));
+        assert_eq!(rates.convert(5.0, "IDR"), None);
+    }
+}
@@ -0,0 +1,70 @@
+// src/expiry.rs
+//
+// Sweep background yang menandai job expired (`jobs.expired_at` + `expired_reason`):
+// - "expiration": `job_offer_expiration_datetime_utc` / `_timestamp` sudah lewat
+// - "not_seen": tidak muncul di pencarian mana pun selama `EXPIRY_NOT_SEEN_DAYS` hari
+//
+// Job expired disembunyikan dari /list kecuali toggle "include expired" aktif. Job yang
+// muncul lagi di hasil pencarian otomatis aktif kembali, kecuali tanggal kedaluwarsa
+// postingnya masih lewat (lihat `Database::mark_job_seen`). Refresh, import dan lazy
+// enrich tidak mengubah status expiry. Menandai expired / terlihat tidak menggeser
+// `updated_at` (urutan /list dan refresh stale tetap), cukup expired_at / last_seen_at.
+
+use std::env;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::database::Database;
+
+#[derive(Debug, Default)]
+pub struct SweepSummary {
+    pub past_expiration: u64,
+    pub not_seen: u64,
+}
+
+/// `EXPIRY_NOT_SEEN_DAYS` (default 30; 0 = hanya pakai tanggal kedaluwarsa posting).
+pub fn not_seen_days_from_env() -> i64 {
+    env::var("EXPIRY_NOT_SEEN_DAYS")
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(30)
+}
+
+pub async fn sweep(db: &Database, not_seen_days: i64) -> Result<SweepSummary, sqlx::Error> {
+    let now = Utc::now();
+    let mut summary = SweepSummary { past_expiration: db.expire_past_offer_date(now).await?, ..Default::default() };
+    if not_seen_days > 0 {
+        let cutoff = now - chrono::Duration::days(not_seen_days);
+        summary.not_seen = db.expire_not_seen_since(cutoff, now).await?;
+    }
+    Ok(summary)
+}
+
+/// Jalankan sweep di task Tokio terpisah tiap `EXPIRY_SWEEP_MINUTES` (default 60; 0 = nonaktif).
+pub fn spawn(db: Database) {
+    let minutes: u64 = env::var("EXPIRY_SWEEP_MINUTES")
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(60);
+    if minutes == 0 {
+        println!("Expiry sweep disabled (EXPIRY_SWEEP_MINUTES=0)");
+        return;
+    }
+    let not_seen_days = not_seen_days_from_env();
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(minutes * 60));
+        loop {
+            ticker.tick().await;
+            match sweep(&db, not_seen_days).await {
+                Ok(s) if s.past_expiration + s.not_seen > 0 => println!(
+                    "expiry sweep: {} lewat tanggal kedaluwarsa, {} tidak terlihat > {not_seen_days} hari",
+                    s.past_expiration, s.not_seen
+                ),
+                Ok(_) => {}
+                Err(e) => eprintln!("expiry sweep error: {e}"),
+            }
+        }
+    });
+}
@@ -0,0 +1,373 @@
+// src/feed_source.rs
+//
+// Sumber lowongan dari feed RSS/Atom (board yang mempublikasikan feed posting).
+// Feed dikonfigurasi lewat `JOB_FEEDS`; tiap entry dipetakan ke `Job` dengan prefix
+// `feed_`, publisher feed di `job_publisher`, dan link entry sebagai opsi apply.
+// URL feed boleh berupa path lokal / `file://` sehingga bisa diuji dengan file feed.
+
+use std::env;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use feed_rs::model::{Entry, Feed, Person};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+
+use crate::database::Database;
+use crate::job_source::{self, JobSource, RunOrigin, SearchPage, SearchRequest};
+use crate::models::{ApplyOption, Job};
+
+/// Satu feed yang dipantau. `publisher` kosong -> pakai judul feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedConfig {
+    pub url: String,
+    pub publisher: Option<String>,
+}
+
+impl FeedConfig {
+    /// Format `JOB_FEEDS`: dipisah koma / baris baru, tiap item `url` atau `Publisher=url`.
+    /// Contoh: `WeWorkRemotely=https://weworkremotely.com/remote-jobs.rss,./fixtures/feeds/example.rss`
+    pub fn parse_list(s: &str) -> Vec<FeedConfig> {
+        s.split([',', '\n'])
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| match item.split_once('=') {
+                // "=" juga bisa muncul di query string URL; anggap publisher hanya jika
+                // bagian kiri bukan URL
+                Some((name, url)) if !name.contains("://") && !name.contains('/') => FeedConfig {
+                    url: url.trim().to_string(),
+                    publisher: Some(name.trim().to_string()).filter(|n| !n.is_empty()),
+                },
+                _ => FeedConfig { url: item.to_string(), publisher: None },
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+pub struct FeedSource {
+    client: Client,
+    feeds: Vec<FeedConfig>,
+}
+
+impl FeedSource {
+    pub fn new(feeds: Vec<FeedConfig>) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("job-finder/0.1")
+                .timeout(Duration::from_secs(30))
+                .build()
+                .expect("build reqwest client"),
+            feeds,
+        }
+    }
+
+    /// Baca daftar feed dari `JOB_FEEDS` (kosong = tidak ada feed).
+    pub fn from_env() -> Self {
+        Self::new(FeedConfig::parse_list(&env::var("JOB_FEEDS").unwrap_or_default()))
+    }
+
+    /// Ambil isi feed: http(s) lewat reqwest, selain itu dibaca sebagai file lokal.
+    async fn load(&self, url: &str) -> Result<Vec<u8>> {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            let resp = self
+                .client
+                .get(url)
+                .send()
+                .await
+                .with_context(|| format!("GET {url}"))?
+                .error_for_status()?;
+            Ok(resp.bytes().await?.to_vec())
+        } else {
+            let path = url.strip_prefix("file://").unwrap_or(url);
+            tokio::fs::read(path).await.with_context(|| format!("read {path}"))
+        }
+    }
+
+    /// Ambil + parse satu feed menjadi daftar `Job`.
+    pub async fn fetch_feed(&self, feed: &FeedConfig) -> Result<Vec<Job>> {
+        let bytes = self.load(&feed.url).await?;
+        parse_feed(&bytes, feed)
+    }
+}
+
+/// Parse isi feed RSS/Atom menjadi `Job` (tanpa I/O, dipakai juga untuk file lokal).
+pub fn parse_feed(bytes: &[u8], feed: &FeedConfig) -> Result<Vec<Job>> {
+    let parsed: Feed = feed_rs::parser::parse(bytes).with_context(|| format!("parse feed {}", feed.url))?;
+
+    let publisher = feed
+        .publisher
+        .clone()
+        .or_else(|| parsed.title.as_ref().map(|t| t.content.trim().to_string()))
+        .filter(|p| !p.is_empty());
+
+    Ok(parsed
+        .entries
+        .iter()
+        .filter_map(|entry| job_from_entry(entry, &feed.url, publisher.as_deref()))
+        .collect())
+}
+
+/// job_id stabil dari id entry (guid) atau link: `feed_` + 32 hex sha256.
+fn feed_job_id(feed_url: &str, key: &str) -> String {
+    let digest = Sha256::digest(format!("{feed_url}\n{key}").as_bytes());
+    let hex: String = digest.iter().take(16).map(|b| format!("{b:02x}")).collect();
+    format!("feed_{hex}")
+}
+
+fn job_from_entry(entry: &Entry, feed_url: &str, publisher: Option<&str>) -> Option<Job> {
+    let link = entry
+        .links
+        .iter()
+        .find(|l| l.rel.as_deref().map(|r| r == "alternate").unwrap_or(true))
+        .or(entry.links.first())
+        .map(|l| l.href.trim().to_string())
+        .filter(|h| !h.is_empty());
+
+    let key = if entry.id.trim().is_empty() { link.clone()? } else { entry.id.trim().to_string() };
+    let job_id = feed_job_id(feed_url, &key);
+
+    let title = entry.title.as_ref().map(|t| t.content.trim().to_string()).filter(|t| !t.is_empty());
+    let description = entry
+        .content
+        .as_ref()
+        .and_then(|c| c.body.clone())
+        .or_else(|| entry.summary.as_ref().map(|s| s.content.clone()))
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    let posted = entry.published.or(entry.updated);
+    let employer = entry.authors.first().and_then(author_name);
+
+    let apply_options = link
+        .iter()
+        .map(|l| ApplyOption {
+            job_id: job_id.clone(),
+            publisher: publisher.map(str::to_string),
+            apply_link: Some(l.clone()),
+            ..Default::default()
+        })
+        .collect();
+
+    let raw = serde_json::json!({
+        "feed_url": feed_url,
+        "id": entry.id,
+        "title": title,
+        "link": link,
+        "published": posted,
+        "authors": entry.authors.iter().map(|a| a.name.clone()).collect::<Vec<_>>(),
+        "categories": entry.categories.iter().map(|c| c.term.clone()).collect::<Vec<_>>(),
+    });
+
+    Some(Job {
+        job_id,
+        employer_name: employer,
+        job_publisher: publisher.map(str::to_string),
+        job_title: title,
+        job_apply_link: link,
+        job_description: description,
+        job_posted_at_timestamp: posted.map(|d| d.timestamp()),
+        job_posted_at_datetime_utc: posted,
+        raw_json: Some(raw),
+        apply_options,
+        ..Default::default()
+    })
+}
+
+/// Nama author entry. RSS `<author>` berbentuk "email (Nama)"; feed-rs menaruhnya di
+/// `email` dengan nama placeholder "author".
+fn author_name(person: &Person) -> Option<String> {
+    let name = person.name.trim();
+    if !name.is_empty() && name != "author" {
+        return Some(name.to_string());
+    }
+    let email = person.email.as_deref()?;
+    let inner = email.split_once('(')?.1.split_once(')')?.0.trim();
+    (!inner.is_empty()).then(|| inner.to_string())
+}
+
+fn matches_query(job: &Job, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return true;
+    }
+    [&job.job_title, &job.employer_name, &job.job_description]
+        .iter()
+        .filter_map(|f| f.as_deref())
+        .any(|f| f.to_lowercase().contains(&query))
+}
+
+#[async_trait]
+impl JobSource for FeedSource {
+    fn name(&self) -> &'static str {
+        "feed"
+    }
+
+    /// Bukan RapidAPI; kunci ini hanya dipakai untuk soft limit (yang selalu lolos).
+    fn host(&self) -> &str {
+        "feeds"
+    }
+
+    /// Ambil semua feed; `query` (opsional) memfilter judul/perusahaan/deskripsi.
+    /// Feed yang gagal dilewati; error hanya jika semua feed gagal.
+    async fn search(&self, req: &SearchRequest) -> anyhow::Result<SearchPage> {
+        if self.feeds.is_empty() {
+            bail!("JOB_FEEDS belum diset");
+        }
+
+        let mut jobs = Vec::new();
+        let mut errors = Vec::new();
+        for feed in &self.feeds {
+            match self.fetch_feed(feed).await {
+                Ok(found) => jobs.extend(found.into_iter().filter(|j| matches_query(j, &req.query))),
+                Err(e) => {
+                    eprintln!("feed {} error: {e:#}", feed.url);
+                    errors.push(format!("{}: {e}", feed.url));
+                }
+            }
+        }
+        if errors.len() == self.feeds.len() {
+            bail!("semua feed gagal: {}", errors.join("; "));
+        }
+
+        let query = req.query.trim();
+        if !query.is_empty() {
+            for job in &mut jobs {
+                job.search_query = Some(query.to_string());
+            }
+        }
+        Ok(SearchPage { jobs, next: None })
+    }
+}
+
+/// Poll feed di background tiap `FEED_POLL_MINUTES` (default 60; 0 = nonaktif).
+pub fn spawn_poller(db: Database, source: FeedSource) {
+    let minutes: u64 = env::var("FEED_POLL_MINUTES")
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(60);
+    if minutes == 0 || source.feeds.is_empty() {
+        println!("Feed poller disabled ({} feed, FEED_POLL_MINUTES={minutes})", source.feeds.len());
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(minutes * 60));
+        loop {
+            ticker.tick().await;
+            let req = SearchRequest::default();
+            let started_at = chrono::Utc::now();
+            let outcome = job_source::fetch_and_store(&source, &req, &db).await.map_err(|e| e.to_string());
+            match &outcome {
+                Ok(s) => println!("feed poll: {} entries ({} new)", s.saved, s.new_job_ids.len()),
+                Err(e) => eprintln!("feed poll error: {e}"),
+            }
+            let outcome = outcome.as_ref().map_err(|e| e.as_str());
+            job_source::record_run(&db, source.name(), RunOrigin::Poller, &req, started_at, outcome, 0).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = "./fixtures/feeds/example.rss";
+    const ATOM: &str = "./fixtures/feeds/example.atom";
+
+    fn config(url: &str) -> FeedConfig {
+        FeedConfig { url: url.to_string(), publisher: None }
+    }
+
+    #[test]
+    fn parses_rss_fixture() {
+        let jobs = parse_feed(include_bytes!("../fixtures/feeds/example.rss"), &config(RSS)).unwrap();
+        assert_eq!(jobs.len(), 2);
+
+        let job = &jobs[0];
+        assert_eq!(job.job_id, feed_job_id(RSS, "example-1001"));
+        assert_eq!(job.job_title.as_deref(), Some("Senior Rust Engineer"));
+        // RSS <author> "email (Nama)"
+        assert_eq!(job.employer_name.as_deref(), Some("Acme Corp"));
+        assert_eq!(job.job_publisher.as_deref(), Some("Example Remote Board"));
+        assert_eq!(job.job_apply_link.as_deref(), Some("https://jobs.example.com/jobs/1001"));
+        assert!(job.job_description.as_deref().unwrap().contains("<strong>Rust</strong>"));
+        assert_eq!(
+            job.job_posted_at_datetime_utc.map(|d| d.to_rfc3339()).as_deref(),
+            Some("2025-09-01T08:00:00+00:00")
+        );
+        assert_eq!(job.job_posted_at_timestamp, job.job_posted_at_datetime_utc.map(|d| d.timestamp()));
+        assert_eq!(job.apply_options.len(), 1);
+        assert_eq!(job.apply_options[0].apply_link, job.job_apply_link);
+        assert_eq!(job.apply_options[0].publisher.as_deref(), Some("Example Remote Board"));
+        let raw = job.raw_json.as_ref().unwrap();
+        assert_eq!(raw["categories"], serde_json::json!(["rust"]));
+
+        assert_eq!(jobs[1].job_title.as_deref(), Some("Backend Developer (Go)"));
+        assert_eq!(jobs[1].employer_name, None);
+    }
+
+    #[test]
+    fn parses_atom_fixture() {
+        let feed = FeedConfig { url: ATOM.to_string(), publisher: Some("Globex Board".into()) };
+        let jobs = parse_feed(include_bytes!("../fixtures/feeds/example.atom"), &feed).unwrap();
+        assert_eq!(jobs.len(), 1);
+
+        let job = &jobs[0];
+        assert_eq!(job.job_id, feed_job_id(ATOM, "urn:example:jobs:2001"));
+        assert_eq!(job.job_title.as_deref(), Some("Data Engineer"));
+        assert_eq!(job.employer_name.as_deref(), Some("Globex"));
+        // publisher dari config menang atas judul feed
+        assert_eq!(job.job_publisher.as_deref(), Some("Globex Board"));
+        assert_eq!(job.job_apply_link.as_deref(), Some("https://atom.example.com/jobs/2001"));
+        assert_eq!(job.job_description.as_deref(), Some("<p>Pipelines with Rust and Kafka.</p>"));
+        assert_eq!(
+            job.job_posted_at_datetime_utc.map(|d| d.to_rfc3339()).as_deref(),
+            Some("2025-09-03T09:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn feed_job_id_is_stable_per_feed() {
+        let id = feed_job_id(RSS, "example-1001");
+        assert!(id.starts_with("feed_") && id.len() == "feed_".len() + 32);
+        assert_eq!(id, feed_job_id(RSS, "example-1001"));
+        assert_ne!(id, feed_job_id(RSS, "example-1002"));
+        assert_ne!(id, feed_job_id(ATOM, "example-1001"));
+    }
+
+    #[test]
+    fn rejects_non_feed_bytes() {
+        assert!(parse_feed(b"not a feed", &config(RSS)).is_err());
+    }
+
+    #[test]
+    fn parses_job_feeds_list() {
+        let feeds = FeedConfig::parse_list(
+            "Board=https://example.com/jobs.rss?a=1, ./fixtures/feeds/example.atom\nhttps://x.example/feed?x=y",
+        );
+        assert_eq!(
+            feeds,
+            vec![
+                FeedConfig { url: "https://example.com/jobs.rss?a=1".into(), publisher: Some("Board".into()) },
+                FeedConfig { url: "./fixtures/feeds/example.atom".into(), publisher: None },
+                FeedConfig { url: "https://x.example/feed?x=y".into(), publisher: None },
+            ]
+        );
+        assert!(FeedConfig::parse_list(" , ").is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_reads_local_feeds_and_filters_by_query() {
+        let source = FeedSource::new(vec![config(RSS), config(&format!("file://{ATOM}"))]);
+        let all = source.search(&SearchRequest::default()).await.unwrap();
+        assert_eq!(all.jobs.len(), 3);
+        assert!(all.next.is_none());
+
+        let req = SearchRequest { query: "kafka".into(), ..Default::default() };
+        let found = source.search(&req).await.unwrap();
+        assert_eq!(found.jobs.len(), 1);
+        assert_eq!(found.jobs[0].job_title.as_deref(), Some("Data Engineer"));
+        assert_eq!(found.jobs[0].search_query.as_deref(), Some("kafka"));
+    }
+}
@@ -5,9 +5,24 @@ use warp::reply::Response;
 use warp::hyper::Body;
 use warp::http;
 use crate::linkedin_client;
+use crate::job_source::{self, Cursor, FetchError, JobSource, RunOrigin, SearchRequest};
 
 
 use crate::{api_client, database, openai_client};
+use crate::usage::UsageTracker;
+use crate::refresh::{self, RefreshOutcome};
+use crate::salary;
+use crate::exchange_rates;
+use crate::feed_source::FeedSource;
+use crate::ats_source::AtsSource;
+use crate::database::{ListFilter, ListSort};
+use crate::models::JobListItem;
+use crate::importer::{self, ImportFormat, ImportReport};
+use crate::search_cache::{self, CachedSource};
+use crate::link_checker::LinkChecker;
+use crate::description;
+use crate::language;
+use crate::workplace::{self, WorkplaceType};
 
 // ==================== Allowlists untuk filter LinkedIn ====================
 const ALLOWED_EXPERIENCE: &[&str] = &[
@@ -65,6 +80,12 @@ pub struct JobRow {
     pub job: crate::models::Job,
     pub preview: String,      // ringkasan 100 kata
     pub has_analysis: bool,   // ada/tidak analisis
+    pub salary: Option<String>,    // gaji posting, fallback estimasi
+    pub salary_is_estimate: bool,
+    pub salary_annual: Option<String>, // gaji posting dinormalisasi per tahun (jika beda)
+    pub workplace: Option<String>, // "Remote (US)" / "Hybrid" / "On-site"
+    pub source_count: i64,    // job ini + duplikat lintas sumber
+    pub links_dead: bool,     // semua link apply terakhir dicek mati
 }
 
 // ==================== Templates ====================
@@ -82,6 +103,27 @@ pub struct IndexTemplate<'a> {
 #[template(path = "jobs.html")]
 pub struct JobsTemplate {
     pub query: String,
+    /// query string filter aktif untuk link sort / pagination
+    pub filter_qs: String,
+    pub include_expired: bool,
+    /// rentang gaji tahunan (kosong = tanpa batas)
+    pub salary_min: String,
+    pub salary_max: String,
+    /// `SALARY_BASE_CURRENCY` + tanggal kurs file
+    pub base_currency: String,
+    pub rates_date: String,
+    /// filter skill aktif + pilihan (skill, jumlah job)
+    pub skill: String,
+    pub skill_options: Vec<(String, i64)>,
+    /// filter bahasa aktif + pilihan (kode, nama, jumlah job)
+    pub language: String,
+    pub language_options: Vec<(String, String, i64)>,
+    /// filter tempat kerja + wilayah remote aktif dan pilihannya (nilai, label, jumlah job)
+    pub workplace: String,
+    pub workplace_options: Vec<(String, String, i64)>,
+    pub region: String,
+    pub region_options: Vec<(String, i64)>,
+    pub sort: String,
     pub rows: Vec<JobRow>,
     pub current_page: usize,
     pub per_page: usize,
@@ -95,6 +137,82 @@ pub struct JobsTemplate {
 pub struct JobTemplate {
     pub job: crate::models::Job,
     pub apply_options: Vec<crate::models::ApplyOption>,
+    pub notice: Option<String>,
+    /// job JSearch -> tombol Refresh (job-details)
+    pub can_refresh: bool,
+    pub salary: Option<String>,
+    /// estimasi JSearch jika gaji posting kosong
+    pub salary_estimate: Option<String>,
+    pub salary_estimate_source: String,
+    /// gaji posting per tahun dalam `SALARY_BASE_CURRENCY` (jika beda dari gaji posting)
+    pub salary_annual: Option<String>,
+    /// skill hasil ekstraksi (tabel job_skills)
+    pub skills: Vec<String>,
+    /// nama bahasa `job_posting_language` (dari sumber atau hasil deteksi)
+    pub language_name: Option<String>,
+    /// "Remote (US)" / "Hybrid" / "On-site" (lihat `workplace`)
+    pub workplace: Option<String>,
+    /// posting yang sama dari sumber lain (job ini kanonik)
+    pub duplicates: Vec<crate::models::DuplicateJob>,
+    /// semua opsi apply sudah dicek dan mati
+    pub links_dead: bool,
+    /// deskripsi Markdown yang sudah di-render + disanitasi
+    pub description_html: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "usage.html")]
+pub struct UsageTemplate {
+    pub days: i64,
+    pub soft_limit_daily: Option<i64>,
+    pub rows: Vec<crate::models::UsageDay>,
+    pub quotas: Vec<crate::models::HostQuota>,
+}
+
+#[derive(Template)]
+#[template(path = "import.html")]
+pub struct ImportTemplate {
+    pub report: Option<ImportReport>,
+    /// error tingkat file (format tidak terbaca, upload gagal)
+    pub error: Option<String>,
+    pub filename: String,
+    /// jumlah semua error per baris (`report.errors` dipotong untuk tampilan)
+    pub error_count: usize,
+}
+
+// ==== satu run saved search + job baru yang ditemukan ====
+#[derive(Debug, Clone)]
+pub struct RunView {
+    pub run: crate::models::SearchRun,
+    pub new_jobs: Vec<crate::models::RunNewJob>,
+}
+
+#[derive(Template)]
+#[template(path = "saved.html")]
+pub struct SavedTemplate {
+    pub searches: Vec<crate::models::SavedSearch>,
+    pub runs: Vec<RunView>,
+}
+
+#[derive(Template)]
+#[template(path = "runs.html")]
+pub struct RunsTemplate {
+    pub runs: Vec<crate::models::SearchRun>,
+    pub sources: Vec<String>,
+    /// filter sumber aktif ("" = semua)
+    pub source: String,
+    pub current_page: usize,
+    pub total_runs: usize,
+    pub total_pages: usize,
+    pub pages: Vec<PageLink>,
+}
+
+#[derive(Template)]
+#[template(path = "run.html")]
+pub struct RunTemplate {
+    pub run: crate::models::SearchRun,
+    pub params: String,
+    pub jobs: Vec<crate::models::SearchRunJob>,
 }
 
 #[derive(Template)]
@@ -173,6 +291,7 @@ pub async fn fetch_handler(
     params: std::collections::HashMap<String, String>,
     db: database::Database,
     api: api_client::JobApiClient,
+    usage: UsageTracker,
 ) -> Result<Response, Rejection> {
     let query       = params.get("query").cloned().unwrap_or_default();
     let country     = params.get("country").cloned().unwrap_or_else(|| "ID".into());
@@ -182,30 +301,186 @@ pub async fn fetch_handler(
     let page: i32 = params.get("page").and_then(|s| s.parse().ok()).unwrap_or(1).max(1);
     let num_pages: i32 = params.get("num_pages").and_then(|s| s.parse().ok()).unwrap_or(1).max(1);
 
-    let results = match api.search(&query, page, num_pages, &date_posted, &country, &language).await {
-        Ok(r) => r,
-        Err(e) => {
+    let req = SearchRequest {
+        query,
+        country: Some(country),
+        language: Some(language),
+        date_posted: Some(date_posted),
+        num_pages,
+        cursor: Cursor::Page(page),
+        ..Default::default()
+    };
+
+    fetch_source_handler(&api, req, db, usage, FetchOptions::from_form(&params)).await
+}
+
+/// Fetch semua feed RSS/Atom (`JOB_FEEDS`), opsional difilter kata kunci.
+pub async fn fetch_feed_handler(
+    params: std::collections::HashMap<String, String>,
+    db: database::Database,
+    feeds: FeedSource,
+    usage: UsageTracker,
+) -> Result<Response, Rejection> {
+    let req = SearchRequest {
+        query: params.get("query").map(|q| q.trim().to_string()).unwrap_or_default(),
+        num_pages: 1,
+        ..Default::default()
+    };
+
+    fetch_source_handler(&feeds, req, db, usage, FetchOptions::from_form(&params)).await
+}
+
+/// Fetch job board ATS publik (Greenhouse / Lever / Ashby) per company slug.
+pub async fn fetch_ats_handler(
+    params: std::collections::HashMap<String, String>,
+    db: database::Database,
+    boards: Vec<AtsSource>,
+    usage: UsageTracker,
+) -> Result<Response, Rejection> {
+    let board = params.get("board").map(|b| b.trim().to_lowercase()).unwrap_or_default();
+    let Some(source) = boards.iter().find(|s| s.name() == board) else {
+        return Ok(
+            warp::reply::with_status(format!("Board tidak dikenal: '{board}'"), StatusCode::BAD_REQUEST)
+                .into_response()
+        );
+    };
+
+    let req = SearchRequest {
+        query: params.get("company").map(|q| q.trim().to_string()).unwrap_or_default(),
+        num_pages: 1,
+        ..Default::default()
+    };
+
+    fetch_source_handler(source, req, db, usage, FetchOptions::from_form(&params)).await
+}
+
+/// Opsi fetch dari form (di luar parameter pencarian).
+struct FetchOptions {
+    /// `schedule_minutes` > 0 = simpan juga sebagai saved search terjadwal.
+    schedule_minutes: Option<i32>,
+    /// `max_pages`: ikuti cursor next sampai N halaman (1..=MAX_FETCH_PAGES).
+    max_pages: usize,
+    /// `resume`: mulai dari cursor terakhir yang tersimpan untuk query ini.
+    resume: bool,
+    /// `force_refresh`: abaikan search_cache, selalu panggil provider.
+    force_refresh: bool,
+}
+
+const MAX_FETCH_PAGES: usize = 20;
+/// Batas item per bulk refresh / estimasi gaji (1 call per item).
+const MAX_REFRESH_BATCH: i64 = 100;
+
+impl FetchOptions {
+    fn from_form(params: &std::collections::HashMap<String, String>) -> Self {
+        Self {
+            schedule_minutes: params
+                .get("schedule_minutes")
+                .and_then(|s| s.trim().parse::<i32>().ok())
+                .filter(|n| *n > 0),
+            max_pages: params
+                .get("max_pages")
+                .and_then(|s| s.trim().parse::<usize>().ok())
+                .unwrap_or(1)
+                .clamp(1, MAX_FETCH_PAGES),
+            resume: params.get("resume").map(|v| v == "1" || v == "on").unwrap_or(false),
+            force_refresh: params.get("force_refresh").map(|v| v == "1" || v == "on").unwrap_or(false),
+        }
+    }
+}
+
+/// Handler bersama untuk semua `JobSource`: search + enrich + simpan (multi halaman),
+/// simpan cursor terakhir per query, lalu redirect ke /list dengan notice.
+async fn fetch_source_handler(
+    source: &dyn JobSource,
+    mut req: SearchRequest,
+    db: database::Database,
+    usage: UsageTracker,
+    opts: FetchOptions,
+) -> Result<Response, Rejection> {
+    let query_key = req.query_key();
+    if opts.resume && req.cursor == Cursor::Start {
+        match db.get_search_cursor(source.name(), &query_key).await {
+            Ok(Some(c)) => req.cursor = Cursor::decode(&c),
+            Ok(None) => {}
+            Err(e) => eprintln!("get_search_cursor error: {e}"),
+        }
+    }
+
+    // search yang sama dalam rentang TTL dilayani dari search_cache (kecuali force refresh);
+    // soft limit hanya dicek untuk halaman yang benar-benar memanggil API
+    let cached = CachedSource::new(source, &db, search_cache::ttl_from_env(), opts.force_refresh).with_usage(&usage);
+    let started_at = chrono::Utc::now();
+    let result = job_source::fetch_and_store_pages(&cached, &req, &db, opts.max_pages).await;
+    let error = result.as_ref().err().map(|e| e.to_string());
+    let outcome = match (&result, &error) {
+        (Ok(s), _) => Ok(s),
+        (Err(_), e) => Err(e.as_deref().unwrap_or_default()),
+    };
+    let run_id =
+        job_source::record_run(&db, source.name(), RunOrigin::Manual, &req, started_at, outcome, cached.hits())
+            .await;
+
+    let summary = match result {
+        Ok(s) => s,
+        Err(e @ FetchError::Source(_)) => {
             return Ok(
-                warp::reply::with_status(format!("API error: {e}"), StatusCode::BAD_GATEWAY)
+                warp::reply::with_status(format!("{} {e}", source.name()), StatusCode::BAD_GATEWAY)
                     .into_response()
             );
         }
-    };
-
-    for job in &results.data {
-        if let Err(e) = db.upsert_job(job).await {
+        Err(e @ FetchError::Db(_)) => {
             return Ok(
-                warp::reply::with_status(
-                    format!("DB error saat menyimpan hasil: {e}"),
-                    StatusCode::INTERNAL_SERVER_ERROR
-                ).into_response()
+                warp::reply::with_status(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response()
             );
         }
+        Err(FetchError::SoftLimit(msg)) => {
+            return Ok(warp::reply::with_status(msg, StatusCode::TOO_MANY_REQUESTS).into_response());
+        }
+    };
+
+    let next_cursor = summary.next.as_ref().and_then(Cursor::encode);
+    if let Err(e) = db.save_search_cursor(source.name(), &query_key, next_cursor.as_deref()).await {
+        eprintln!("save_search_cursor error: {e}");
     }
 
-    let uri: http::Uri = format!("/list?q={}", urlencoding::encode(&query))
-        .parse()
-        .unwrap();
+    // Redirect dengan notice
+    let mut notice = format!(
+        "{} fetched: {} items ({} new) dari {} halaman",
+        source.name(), summary.saved, summary.new_job_ids.len(), summary.pages
+    );
+    if cached.hits() > 0 {
+        notice.push_str(&format!(" ({} halaman dari cache)", cached.hits()));
+    }
+    if source.enriches() {
+        notice.push_str(&format!(
+            "; enrich: {} ok, {} skipped, {} failed",
+            summary.enriched, summary.skipped, summary.failed
+        ));
+    }
+    if let Some(msg) = &summary.limit_reached {
+        notice.push_str(&format!("; berhenti: {msg}"));
+    }
+    if summary.next.is_some() {
+        notice.push_str(" (has next page; cursor disimpan, pilih 'lanjutkan' untuk meneruskan)");
+    }
+    if let Some(id) = run_id {
+        notice.push_str(&format!("; run #{id} (lihat /runs/{id})"));
+    }
+    if let Some(minutes) = opts.schedule_minutes {
+        match db.insert_saved_search(source.name(), &req, minutes).await {
+            Ok(id) => notice.push_str(&format!("; saved search #{id} tiap {minutes} menit")),
+            Err(e) => eprintln!("insert_saved_search error: {e}"),
+        }
+    }
+
+    let uri: http::Uri = format!(
+        "/list?q={}&notice={}",
+        urlencoding::encode(&req.query),
+        urlencoding::encode(&notice)
+    )
+    .parse()
+    .unwrap();
 
     let resp = warp::http::Response::builder()
         .status(StatusCode::SEE_OTHER)
@@ -216,26 +491,33 @@ pub async fn fetch_handler(
 }
 
 pub async fn list_handler(
-    query: Option<String>,
+    filter: ListFilter,
+    sort: ListSort,
     page: usize,
     db: crate::database::Database,
 ) -> Result<Response, Rejection> {
     const PER_PAGE: usize = 50;
 
-    let total_jobs_i64 = db.count_jobs(query.as_deref()).await.map_err(|_| warp::reject())?;
+    let total_jobs_i64 = db.count_jobs(&filter).await.map_err(|_| warp::reject())?;
     let total_jobs = total_jobs_i64 as usize;
     let total_pages = (total_jobs + PER_PAGE - 1).max(1) / PER_PAGE;
 
     let current_page = page.clamp(1, total_pages.max(1));
 
     let jobs = db
-        .list_jobs_paged(query.as_deref(), current_page as i64, PER_PAGE as i64)
+        .list_jobs_paged(&filter, sort, current_page as i64, PER_PAGE as i64)
         .await
         .map_err(|_| warp::reject())?;
 
     let rows: Vec<JobRow> = jobs
         .into_iter()
-        .map(|job| {
+        .map(|item| {
+            let (salary, salary_is_estimate) = list_salary(&item);
+            let salary_annual = salary::annual_label(&item.job);
+            let workplace = workplace::display_label(&item.job);
+            let source_count = item.source_count();
+            let links_dead = item.links_dead();
+            let job = item.job;
             let text = job.matching_analysis.clone();
             let mut iter = text.split_whitespace();
             let first_100: Vec<&str> = iter.by_ref().take(100).collect();
@@ -251,6 +533,12 @@ pub async fn list_handler(
                 job,
                 preview,
                 has_analysis,
+                salary,
+                salary_is_estimate,
+                salary_annual,
+                workplace,
+                source_count,
+                links_dead,
             }
         })
         .collect();
@@ -261,8 +549,56 @@ pub async fn list_handler(
         .map(|n| PageLink { n, is_current: n == current_page })
         .collect();
 
+    // gagal ambil pilihan skill tidak fatal: dropdown cukup berisi skill aktif
+    let mut skill_options = db.skill_counts(200).await.unwrap_or_else(|e| {
+        eprintln!("skill_counts error: {e}");
+        Vec::new()
+    });
+    if let Some(skill) = &filter.skill {
+        if !skill_options.iter().any(|(s, _)| s == skill) {
+            skill_options.insert(0, (skill.clone(), 0));
+        }
+    }
+
+    let mut language_options: Vec<(String, String, i64)> = db
+        .language_counts()
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("language_counts error: {e}");
+            Vec::new()
+        })
+        .into_iter()
+        .map(|(code, n)| {
+            let name = language::display_name(&code);
+            (code, name, n)
+        })
+        .collect();
+    if let Some(lang) = &filter.language {
+        if !language_options.iter().any(|(c, _, _)| c == lang) {
+            language_options.insert(0, (lang.clone(), language::display_name(lang), 0));
+        }
+    }
+
+    let (workplace_options, region_options) = workplace_filter_options(&db, &filter).await;
+
+    let rates = exchange_rates::global();
     let page_ctx = JobsTemplate {
-        query: query.unwrap_or_default(),
+        query: filter.query.clone().unwrap_or_default(),
+        filter_qs: filter.to_query_string(),
+        include_expired: filter.include_expired,
+        salary_min: filter.salary_min.map(|v| v.to_string()).unwrap_or_default(),
+        salary_max: filter.salary_max.map(|v| v.to_string()).unwrap_or_default(),
+        base_currency: rates.target.clone(),
+        rates_date: rates.date.clone().unwrap_or_default(),
+        skill: filter.skill.clone().unwrap_or_default(),
+        skill_options,
+        language: filter.language.clone().unwrap_or_default(),
+        language_options,
+        workplace: filter.workplace.clone().unwrap_or_default(),
+        workplace_options,
+        region: filter.region.clone().unwrap_or_default(),
+        region_options,
+        sort: sort.as_param().to_string(),
         rows,
         current_page,
         per_page: PER_PAGE,
@@ -275,13 +611,77 @@ pub async fn list_handler(
     Ok(warp::reply::html(html).into_response())
 }
 
+/// Pilihan dropdown tempat kerja (nilai, label, jumlah) dan wilayah remote (wilayah, jumlah).
+/// Nilai gabungan "US, Canada" dihitung ke masing-masing wilayah. Gagal tidak fatal.
+async fn workplace_filter_options(
+    db: &crate::database::Database,
+    filter: &ListFilter,
+) -> (Vec<(String, String, i64)>, Vec<(String, i64)>) {
+    let counts = db.workplace_counts().await.unwrap_or_else(|e| {
+        eprintln!("workplace_counts error: {e}");
+        Vec::new()
+    });
+
+    let types = [WorkplaceType::Remote, WorkplaceType::Hybrid, WorkplaceType::Onsite];
+    let workplace_options = types
+        .iter()
+        .map(|t| {
+            let n = counts.iter().filter(|(wt, _, _)| wt == t.as_str()).map(|(_, _, n)| n).sum();
+            (t.as_str().to_string(), t.label().to_string(), n)
+        })
+        .collect();
+
+    let mut regions: Vec<(String, i64)> = Vec::new();
+    for (_, region, n) in counts.iter().filter(|(wt, _, _)| wt == WorkplaceType::Remote.as_str()) {
+        for r in region.as_deref().unwrap_or("").split(", ").filter(|r| !r.is_empty()) {
+            match regions.iter_mut().find(|(name, _)| name == r) {
+                Some(entry) => entry.1 += n,
+                None => regions.push((r.to_string(), *n)),
+            }
+        }
+    }
+    regions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    if let Some(region) = &filter.region {
+        if !regions.iter().any(|(r, _)| r == region) {
+            regions.insert(0, (region.clone(), 0));
+        }
+    }
+    (workplace_options, regions)
+}
+
+/// Gaji untuk kolom /list: gaji posting, atau estimasi (flag `true`) jika kosong.
+fn list_salary(item: &JobListItem) -> (Option<String>, bool) {
+    let job = &item.job;
+    let posted = salary::salary_label(
+        job.job_min_salary.as_ref(),
+        job.job_max_salary.as_ref(),
+        job.job_salary_currency.as_deref(),
+        job.job_salary_period.as_deref(),
+    );
+    if posted.is_some() {
+        return (posted, false);
+    }
+
+    let (min, max) = match (&item.est_min_salary, &item.est_max_salary) {
+        (None, None) => (&item.est_median_salary, &item.est_median_salary),
+        (min, max) => (min, max),
+    };
+    let est = salary::salary_label(
+        min.as_ref(),
+        max.as_ref(),
+        item.est_salary_currency.as_deref(),
+        item.est_salary_period.as_deref(),
+    );
+    let is_est = est.is_some();
+    (est, is_est)
+}
+
 pub async fn view_handler(
     job_id: String,
+    notice: Option<String>,
     db: database::Database,
     li: linkedin_client::LinkedInApiClient, // <-- baru
 ) -> Result<Response, Rejection> {
-    use serde_json::json;
-
     // helper kecil
     fn is_blank(opt: Option<&String>) -> bool {
         opt.map(|s| s.trim().is_empty()).unwrap_or(true)
@@ -295,33 +695,17 @@ pub async fn view_handler(
         let is_linkedin = job.job_id.starts_with("li_");
         let desc_kosong = is_blank(job.job_description.as_ref());
         if is_linkedin && desc_kosong {
-            // strip prefix: "li_4293585810" -> "4293585810"
-            let li_id = job.job_id.strip_prefix("li_").unwrap_or(&job.job_id).to_string();
-
-            match li.get_job(&li_id).await {
-                Ok(v) => {
-                    // API bisa return {"data": {...}} atau langsung {...}
-                    let detail_obj = v.get("data").cloned().unwrap_or(v);
-
-                    // upsert ke DB dengan mapper kamu
-                    if let Err(e) = db.upsert_job_from_linkedin(&detail_obj).await {
-                        eprintln!("upsert_job_from_linkedin error: {e}");
-                    } else {
-                        // optional: tambahkan apply option LinkedIn bila belum ada
-                        let link = detail_obj
-                            .get("linkedinUrl")
-                            .and_then(|x| x.as_str())
-                            .map(|s| s.to_string());
-                        if let Err(e) = db.insert_apply_option_if_new(&job_id, "LinkedIn", link, None).await {
-                            eprintln!("insert_apply_option_if_new error: {e}");
-                        }
+            match li.enrich(job.clone()).await {
+                Ok(detail) => {
+                    if let Err(e) = job_source::store_job(&db, &detail).await {
+                        eprintln!("store_job({job_id}) error: {e}");
                     }
 
                     // refetch dari DB agar dapat description terbaru
                     job_opt = db.find_job(&job_id).await.map_err(|_| warp::reject())?;
                 }
                 Err(e) => {
-                    eprintln!("LinkedIn get_job({li_id}) error: {e}");
+                    eprintln!("LinkedIn enrich({job_id}) error: {e}");
                     // biarkan lanjut render tanpa description
                 }
             }
@@ -334,7 +718,54 @@ pub async fn view_handler(
 
     match (job_opt, opts) {
         (Some(job), Ok(apply_options)) => {
-            let page = JobTemplate { job, apply_options };
+            let can_refresh = job_source::is_jsearch_job_id(&job.job_id);
+            let salary = salary::salary_label(
+                job.job_min_salary.as_ref(),
+                job.job_max_salary.as_ref(),
+                job.job_salary_currency.as_deref(),
+                job.job_salary_period.as_deref(),
+            );
+            // gaji kosong -> estimasi JSearch dari cache salary_estimates (tanpa call API); gagal tidak fatal
+            let estimate = salary::cached_estimate_for_job(&db, &job).await.unwrap_or_else(|e| {
+                eprintln!("salary estimate({job_id}) error: {e}");
+                None
+            });
+            let salary_estimate = estimate.as_ref().and_then(salary::estimate_label);
+            let salary_estimate_source = estimate.and_then(|e| e.publishers).unwrap_or_default();
+            let salary_annual = salary::annual_label(&job);
+            let skills = db.job_skills(&job.job_id).await.unwrap_or_else(|e| {
+                eprintln!("job_skills({job_id}) error: {e}");
+                Vec::new()
+            });
+            let workplace = workplace::display_label(&job);
+            let language_name = job
+                .job_posting_language
+                .as_deref()
+                .filter(|l| !l.trim().is_empty())
+                .map(language::display_name);
+            let duplicates = db.job_duplicates(&job.job_id).await.unwrap_or_else(|e| {
+                eprintln!("job_duplicates({job_id}) error: {e}");
+                Vec::new()
+            });
+            let links_dead = !apply_options.is_empty()
+                && apply_options.iter().all(|o| o.link_alive == Some(false));
+            let description_html = description::detail_html(&job);
+            let page = JobTemplate {
+                job,
+                apply_options,
+                notice,
+                can_refresh,
+                salary,
+                salary_estimate,
+                salary_estimate_source,
+                salary_annual,
+                skills,
+                language_name,
+                workplace,
+                duplicates,
+                links_dead,
+                description_html,
+            };
             let html = page.render().unwrap_or_else(|e| format!("Template error: {e}"));
             Ok(warp::reply::html(html).into_response())
         }
@@ -347,6 +778,162 @@ pub async fn view_handler(
 
 }
 
+/// Refresh satu job JSearch dari `/job-details`, lalu kembali ke halaman detail.
+pub async fn refresh_handler(
+    job_id: String,
+    db: database::Database,
+    api: api_client::JobApiClient,
+    usage: UsageTracker,
+) -> Result<Response, Rejection> {
+    match db.find_job(&job_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Ok(warp::reply::with_status("Job not found", StatusCode::NOT_FOUND).into_response())
+        }
+        Err(e) => {
+            return Ok(
+                warp::reply::with_status(format!("DB error: {e}"), StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response()
+            )
+        }
+    }
+    if !job_source::is_jsearch_job_id(&job_id) {
+        return Ok(
+            warp::reply::with_status("Refresh hanya untuk job JSearch", StatusCode::BAD_REQUEST)
+                .into_response()
+        );
+    }
+    if let Err(msg) = usage.check_soft_limit(api.host()).await {
+        return Ok(warp::reply::with_status(msg, StatusCode::TOO_MANY_REQUESTS).into_response());
+    }
+
+    let notice = match refresh::refresh_job(&api, &db, &job_id).await {
+        Ok(RefreshOutcome::Updated) => "Job diperbarui dari JSearch".to_string(),
+        Ok(RefreshOutcome::Missing) => "Posting sudah tidak ada di JSearch; ditandai hilang".to_string(),
+        Err(e @ FetchError::Source(_)) => {
+            return Ok(warp::reply::with_status(e.to_string(), StatusCode::BAD_GATEWAY).into_response())
+        }
+        Err(e @ FetchError::Db(_)) => {
+            return Ok(
+                warp::reply::with_status(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response()
+            )
+        }
+        Err(FetchError::SoftLimit(msg)) => {
+            return Ok(warp::reply::with_status(msg, StatusCode::TOO_MANY_REQUESTS).into_response())
+        }
+    };
+
+    let resp = warp::http::Response::builder()
+        .status(StatusCode::SEE_OTHER)
+        .header("Location", format!("/view/{job_id}?notice={}", urlencoding::encode(&notice)))
+        .body(Body::empty())
+        .unwrap();
+    Ok(resp)
+}
+
+/// Bulk refresh job JSearch yang basi (updated_at lebih lama dari `hours` jam).
+pub async fn refresh_stale_handler(
+    hours: i64,
+    limit: i64,
+    db: database::Database,
+    api: api_client::JobApiClient,
+    usage: UsageTracker,
+) -> Result<Response, Rejection> {
+    let hours = hours.max(1);
+    let limit = limit.clamp(1, MAX_REFRESH_BATCH);
+
+    let summary = match refresh::refresh_stale(&api, &db, &usage, hours, limit).await {
+        Ok(s) => s,
+        Err(e) => {
+            return Ok(
+                warp::reply::with_status(format!("DB error: {e}"), StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response()
+            )
+        }
+    };
+
+    let mut notice = format!(
+        "refresh > {hours} jam: {} dicek, {} diperbarui, {} hilang, {} gagal",
+        summary.checked, summary.updated, summary.missing, summary.failed
+    );
+    if let Some(msg) = summary.stopped {
+        notice.push_str(&format!(" (berhenti: {msg})"));
+    }
+
+    let resp = warp::http::Response::builder()
+        .status(StatusCode::SEE_OTHER)
+        .header("Location", format!("/list?notice={}", urlencoding::encode(&notice)))
+        .body(Body::empty())
+        .unwrap();
+    Ok(resp)
+}
+
+/// Isi cache estimasi gaji untuk job tanpa gaji (maksimal `limit` judul+lokasi).
+pub async fn estimate_salaries_handler(
+    limit: i64,
+    db: database::Database,
+    api: api_client::JobApiClient,
+    usage: UsageTracker,
+) -> Result<Response, Rejection> {
+    let limit = limit.clamp(1, MAX_REFRESH_BATCH);
+
+    let summary = match salary::estimate_missing(&api, &db, &usage, limit).await {
+        Ok(s) => s,
+        Err(e) => {
+            return Ok(
+                warp::reply::with_status(format!("DB error: {e}"), StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response()
+            )
+        }
+    };
+
+    let mut notice = format!(
+        "estimasi gaji: {} judul dicek, {} ada estimasi, {} gagal",
+        summary.checked, summary.found, summary.failed
+    );
+    if let Some(msg) = summary.stopped {
+        notice.push_str(&format!(" (berhenti: {msg})"));
+    }
+
+    let resp = warp::http::Response::builder()
+        .status(StatusCode::SEE_OTHER)
+        .header("Location", format!("/list?sort=salary&notice={}", urlencoding::encode(&notice)))
+        .body(Body::empty())
+        .unwrap();
+    Ok(resp)
+}
+
+/// Cek liveness maksimal `limit` link apply sekarang (di luar jadwal background).
+pub async fn check_links_handler(
+    limit: i64,
+    db: database::Database,
+    checker: LinkChecker,
+) -> Result<Response, Rejection> {
+    let limit = limit.clamp(1, MAX_REFRESH_BATCH);
+
+    let summary = match checker.run_batch(&db, limit).await {
+        Ok(s) => s,
+        Err(e) => {
+            return Ok(
+                warp::reply::with_status(format!("DB error: {e}"), StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response()
+            )
+        }
+    };
+
+    let notice = format!(
+        "cek link apply: {} dicek, {} hidup, {} mati, {} tidak pasti",
+        summary.checked, summary.alive, summary.dead, summary.unknown
+    );
+    let resp = warp::http::Response::builder()
+        .status(StatusCode::SEE_OTHER)
+        .header("Location", format!("/list?notice={}", urlencoding::encode(&notice)))
+        .body(Body::empty())
+        .unwrap();
+    Ok(resp)
+}
+
 pub async fn analyze_handler(
     params: std::collections::HashMap<String, String>,
     db: database::Database,
@@ -452,6 +1039,7 @@ pub async fn fetch_linkedin_handler(
     params: std::collections::HashMap<String, String>,
     db: database::Database,
     li: linkedin_client::LinkedInApiClient,
+    usage: UsageTracker,
 ) -> Result<Response, Rejection> {
     // Ambil & bersihkan input
     let query            = params.get("query").cloned().unwrap_or_else(|| "kotlin".into());
@@ -472,79 +1060,267 @@ pub async fn fetch_linkedin_handler(
     // penting: None jika kosong, agar tidak mengirim nextToken=
     let next_token       = none_if_empty(params.get("next_token").cloned());
 
-    // Panggil /v2/linkedin/search
-    let search_json = match li.search(
-        &query,
-        experience_lvls.as_deref(),
-        workplace_types.as_deref(),
-        location.as_deref(),
-        date_posted.as_deref(),
-        employment_types.as_deref(),
-        next_token.as_deref(),
-    ).await {
-        Ok(v) => v,
+    let req = SearchRequest {
+        query,
+        date_posted,
+        location,
+        experience_levels: experience_lvls,
+        workplace_types,
+        employment_types,
+        cursor: next_token.map(Cursor::Token).unwrap_or_default(),
+        ..Default::default()
+    };
+
+    fetch_source_handler(&li, req, db, usage, FetchOptions::from_form(&params)).await
+}
+
+/// Daftar saved search + run terakhir (dengan job yang baru terlihat).
+pub async fn saved_handler(db: database::Database) -> Result<Response, Rejection> {
+    let searches = db.list_saved_searches().await;
+    let runs = db.recent_saved_search_runs(30).await;
+
+    let (searches, runs) = match (searches, runs) {
+        (Ok(s), Ok(r)) => (s, r),
+        (Err(e), _) | (_, Err(e)) => {
+            return Ok(
+                warp::reply::with_status(format!("DB error: {e}"), StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response()
+            )
+        }
+    };
+
+    let mut by_run: std::collections::HashMap<u64, Vec<crate::models::RunNewJob>> =
+        std::collections::HashMap::new();
+    if let Some(min_run_id) = runs.iter().map(|r| r.id).min() {
+        let new_jobs = db.new_jobs_since_run(min_run_id).await.unwrap_or_else(|e| {
+            eprintln!("new_jobs_since_run error: {e}");
+            Vec::new()
+        });
+        for j in new_jobs {
+            by_run.entry(j.run_id).or_default().push(j);
+        }
+    }
+
+    let runs: Vec<RunView> = runs
+        .into_iter()
+        .map(|run| {
+            let new_jobs = by_run.remove(&run.id).unwrap_or_default();
+            RunView { run, new_jobs }
+        })
+        .collect();
+
+    let page = SavedTemplate { searches, runs };
+    let html = page.render().unwrap_or_else(|e| format!("Template error: {e}"));
+    Ok(warp::reply::html(html).into_response())
+}
+
+pub async fn saved_delete_handler(id: u64, db: database::Database) -> Result<Response, Rejection> {
+    if let Err(e) = db.delete_saved_search(id).await {
+        return Ok(
+            warp::reply::with_status(format!("DB error: {e}"), StatusCode::INTERNAL_SERVER_ERROR)
+                .into_response()
+        );
+    }
+
+    let resp = warp::http::Response::builder()
+        .status(StatusCode::FOUND)
+        .header("Location", "/saved")
+        .body(Body::empty())
+        .unwrap();
+    Ok(resp)
+}
+
+/// Riwayat semua fetch (manual, scheduler, poller) dari tabel search_runs.
+pub async fn runs_handler(
+    source: Option<String>,
+    page: usize,
+    db: database::Database,
+) -> Result<Response, Rejection> {
+    const PER_PAGE: usize = 50;
+    let source = none_if_empty(source);
+    let current_page = page.max(1);
+
+    let listed = db.list_search_runs(source.as_deref(), current_page, PER_PAGE).await;
+    let sources = db.search_run_sources().await;
+    let ((runs, total), sources) = match (listed, sources) {
+        (Ok(l), Ok(s)) => (l, s),
+        (Err(e), _) | (_, Err(e)) => {
+            return Ok(
+                warp::reply::with_status(format!("DB error: {e}"), StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response()
+            )
+        }
+    };
+
+    let total_runs = total.max(0) as usize;
+    let total_pages = total_runs.div_ceil(PER_PAGE);
+    let start = current_page.saturating_sub(3).max(1);
+    let end = (current_page + 3).min(total_pages.max(1));
+    let pages: Vec<PageLink> = (start..=end)
+        .map(|n| PageLink { n, is_current: n == current_page })
+        .collect();
+
+    let page_ctx = RunsTemplate {
+        runs,
+        sources,
+        source: source.unwrap_or_default(),
+        current_page,
+        total_runs,
+        total_pages,
+        pages,
+    };
+    let html = page_ctx.render().unwrap_or_else(|e| format!("Template error: {e}"));
+    Ok(warp::reply::html(html).into_response())
+}
+
+/// Detail satu run: parameter pencarian + job yang dihasilkan.
+pub async fn run_detail_handler(id: u64, db: database::Database) -> Result<Response, Rejection> {
+    let run = match db.find_search_run(id).await {
+        Ok(Some(r)) => r,
+        Ok(None) => {
+            return Ok(warp::reply::with_status("Run not found", StatusCode::NOT_FOUND).into_response())
+        }
         Err(e) => {
             return Ok(
-                warp::reply::with_status(format!("LinkedIn Search error: {e}"), StatusCode::BAD_GATEWAY)
+                warp::reply::with_status(format!("DB error: {e}"), StatusCode::INTERNAL_SERVER_ERROR)
                     .into_response()
-            );
+            )
+        }
+    };
+
+    let jobs = db.search_run_jobs(id).await.unwrap_or_else(|e| {
+        eprintln!("search_run_jobs error: {e}");
+        Vec::new()
+    });
+    let params = run
+        .params_json
+        .as_ref()
+        .and_then(|v| serde_json::to_string_pretty(v).ok())
+        .unwrap_or_default();
+
+    let page = RunTemplate { run, params, jobs };
+    let html = page.render().unwrap_or_else(|e| format!("Template error: {e}"));
+    Ok(warp::reply::html(html).into_response())
+}
+
+/// Rekap pemakaian RapidAPI: call per hari per host + sisa kuota terakhir.
+pub async fn usage_handler(
+    days: i64,
+    db: database::Database,
+    usage: UsageTracker,
+) -> Result<Response, Rejection> {
+    let days = days.clamp(1, 90);
+
+    let rows = match db.usage_per_day(days).await {
+        Ok(r) => r,
+        Err(e) => {
+            return Ok(
+                warp::reply::with_status(format!("DB error: {e}"), StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response()
+            )
         }
     };
+    let quotas = db.latest_quota_per_host().await.unwrap_or_else(|e| {
+        eprintln!("latest_quota_per_host error: {e}");
+        Vec::new()
+    });
 
-    // data: [ { id, title, companyName, location, datePosted, ... } ]
-    let items = search_json.get("data").and_then(|d| d.as_array()).cloned().unwrap_or_default();
-    let mut saved = 0usize;
+    let page = UsageTemplate { days, soft_limit_daily: usage.soft_limit_daily(), rows, quotas };
+    let html = page.render().unwrap_or_else(|e| format!("Template error: {e}"));
+    Ok(warp::reply::html(html).into_response())
+}
 
-    // Ambil nextToken untuk paging berikutnya (jika ada)
-    let next_token_new = search_json
-        .get("meta").and_then(|m| m.get("nextToken")).and_then(|v| v.as_str()).map(|s| s.to_string())
-        .or_else(|| search_json.get("nextToken").and_then(|v| v.as_str()).map(|s| s.to_string()));
+/// Maks error per baris yang ditampilkan di halaman import.
+const MAX_IMPORT_ERRORS_SHOWN: usize = 200;
+
+fn render_import(page: ImportTemplate) -> Result<Response, Rejection> {
+    let html = page.render().unwrap_or_else(|e| format!("Template error: {e}"));
+    Ok(warp::reply::html(html).into_response())
+}
 
-    // Enrichment per item: /v2/linkedin/get
-    for item in items {
-        let id_str = item.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
-        if id_str.is_empty() { continue; }
+/// Halaman upload import (GET /import).
+pub async fn import_page_handler() -> Result<Response, Rejection> {
+    render_import(ImportTemplate { report: None, error: None, filename: String::new(), error_count: 0 })
+}
 
-        let detail = match li.get_job(&id_str).await {
-            Ok(v) => v,
+/// Upload file import (POST /import, multipart: `file`, `format`, `csv_map`).
+pub async fn import_upload_handler(
+    form: warp::multipart::FormData,
+    db: database::Database,
+) -> Result<Response, Rejection> {
+    use futures::TryStreamExt;
+    use warp::hyper::body::Buf;
+
+    let mut filename = String::new();
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut fields: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+
+    let parts: Result<Vec<warp::multipart::Part>, warp::Error> = form.try_collect().await;
+    let parts = match parts {
+        Ok(p) => p,
+        Err(e) => {
+            return render_import(ImportTemplate {
+                report: None,
+                error: Some(format!("Upload gagal: {e}")),
+                filename,
+                error_count: 0,
+            })
+        }
+    };
+    for part in parts {
+        let name = part.name().to_string();
+        if name == "file" {
+            filename = part.filename().unwrap_or_default().to_string();
+        }
+        let data = part
+            .stream()
+            .try_fold(Vec::new(), |mut acc, buf| async move {
+                acc.extend_from_slice(buf.chunk());
+                Ok(acc)
+            })
+            .await;
+        let data = match data {
+            Ok(d) => d,
             Err(e) => {
-                eprintln!("get_job({id_str}) error: {e}");
-                serde_json::json!({ "data": item })  // ⬅️ pakai path penuh
+                return render_import(ImportTemplate {
+                    report: None,
+                    error: Some(format!("Upload gagal: {e}")),
+                    filename,
+                    error_count: 0,
+                })
             }
         };
-
-        let detail_obj = detail.get("data").cloned().unwrap_or(detail.clone());
-
-        if let Err(e) = db.upsert_job_from_linkedin(&detail_obj).await {
-            eprintln!("upsert_job_from_linkedin error: {e}");
+        if name == "file" {
+            bytes = data;
         } else {
-            let job_id = format!("li_{}", id_str);
-            let link   = detail_obj.get("linkedinUrl").and_then(|v| v.as_str()).map(|s| s.to_string());
-            if let Err(e) = db.insert_apply_option_if_new(&job_id, "LinkedIn", link, None).await {
-                eprintln!("insert_apply_option_if_new error: {e}");
-            }
-            saved += 1;
+            fields.insert(name, String::from_utf8_lossy(&data).into_owned());
         }
     }
 
-    // Redirect dengan notice (+ next_token jika ada)
-    let mut notice = format!("LinkedIn fetched: {saved} items");
-    let mut qs = format!("q={}", urlencoding::encode(&query));
-    if let Some(nt) = next_token_new {
-        notice.push_str(" (has next page)");
-        qs.push_str(&format!("&next_token={}", urlencoding::encode(&nt)));
+    if bytes.is_empty() {
+        return render_import(ImportTemplate {
+            report: None,
+            error: Some("File kosong atau belum dipilih.".into()),
+            filename,
+            error_count: 0,
+        });
     }
 
-    let uri: http::Uri = format!("/list?{}&notice={}", qs, urlencoding::encode(&notice))
-        .parse()
-        .unwrap();
-
-    let resp = warp::http::Response::builder()
-        .status(StatusCode::SEE_OTHER)
-        .header("Location", uri.to_string())
-        .body(Body::empty())
-        .unwrap();
-    Ok(resp)
+    let format = fields
+        .get("format")
+        .and_then(|f| ImportFormat::from_param(f))
+        .unwrap_or_else(|| ImportFormat::detect(&filename, &bytes));
+    let csv_map = fields.get("csv_map").map(|s| s.as_str()).unwrap_or("");
+
+    let page = match importer::import_bytes(&db, &bytes, format, csv_map).await {
+        Ok(mut report) => {
+            let error_count = report.errors.len();
+            report.errors.truncate(MAX_IMPORT_ERRORS_SHOWN);
+            ImportTemplate { report: Some(report), error: None, filename, error_count }
+        }
+        Err(e) => ImportTemplate { report: None, error: Some(e), filename, error_count: 0 },
+    };
+    render_import(page)
 }
 
 pub async fn cover_generate_handler(
@@ -0,0 +1,632 @@
+// src/importer.rs
+//
+// Import massal job dari file: JSON berbentuk JSearch (array job / response
+// `{ "data": [...] }`), NDJSON (satu `raw_json` per baris), atau CSV dengan kolom
+// yang dipetakan ke field `Job`. Setiap baris divalidasi lalu di-upsert lewat
+// `job_source::store_job`; error dilaporkan per baris tanpa menghentikan import.
+// Dipakai halaman /import dan CLI `job_finder import <file>`.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use sqlx::types::BigDecimal;
+
+use crate::database::Database;
+use crate::job_source;
+use crate::models::{ApplyOption, Job};
+
+/// Prefix job_id untuk baris tanpa id (dibentuk dari hash judul/perusahaan/lokasi/link).
+pub const IMPORT_PREFIX: &str = "import_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Json,
+    Ndjson,
+    Csv,
+}
+
+impl ImportFormat {
+    pub fn from_param(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "json" => Some(ImportFormat::Json),
+            "ndjson" | "jsonl" => Some(ImportFormat::Ndjson),
+            "csv" => Some(ImportFormat::Csv),
+            _ => None,
+        }
+    }
+
+    /// Tebak dari ekstensi file, lalu dari isi.
+    pub fn detect(filename: &str, bytes: &[u8]) -> Self {
+        let lower = filename.to_lowercase();
+        if lower.ends_with(".csv") {
+            return ImportFormat::Csv;
+        }
+        if lower.ends_with(".ndjson") || lower.ends_with(".jsonl") {
+            return ImportFormat::Ndjson;
+        }
+        let text = String::from_utf8_lossy(&bytes[..bytes.len().min(4096)]);
+        let trimmed = text.trim_start_matches('\u{FEFF}').trim_start();
+        if trimmed.starts_with('[') {
+            ImportFormat::Json
+        } else if trimmed.starts_with('{') {
+            // satu objek per baris -> NDJSON, selain itu JSON biasa
+            let first_line = trimmed.lines().next().unwrap_or("");
+            if serde_json::from_str::<Value>(first_line).is_ok() && trimmed.lines().nth(1).is_some() {
+                ImportFormat::Ndjson
+            } else {
+                ImportFormat::Json
+            }
+        } else {
+            ImportFormat::Csv
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImportFormat::Json => "json",
+            ImportFormat::Ndjson => "ndjson",
+            ImportFormat::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RowError {
+    /// nomor baris/item (1-based; CSV dihitung tanpa header)
+    pub row: usize,
+    pub job_id: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub format: String,
+    pub total: usize,
+    pub imported: usize,
+    pub new: usize,
+    pub errors: Vec<RowError>,
+}
+
+/// Parse + validasi + upsert. Error file (mis. JSON rusak) dikembalikan sebagai `Err`;
+/// error per baris masuk ke `report.errors`.
+pub async fn import_bytes(
+    db: &Database,
+    bytes: &[u8],
+    format: ImportFormat,
+    csv_map: &str,
+) -> Result<ImportReport, String> {
+    let rows = parse_rows(bytes, format, csv_map)?;
+    let mut report = ImportReport { format: format.name().to_string(), total: rows.len(), ..Default::default() };
+
+    for (idx, row) in rows.into_iter().enumerate() {
+        let row_no = idx + 1;
+        let job = match row {
+            Ok(job) => {
+                let job_id = Some(job.job_id.trim().to_string()).filter(|id| !id.is_empty());
+                match validate(job) {
+                    Ok(job) => job,
+                    Err(message) => {
+                        report.errors.push(RowError { row: row_no, job_id, message });
+                        continue;
+                    }
+                }
+            }
+            Err(message) => {
+                report.errors.push(RowError { row: row_no, job_id: None, message });
+                continue;
+            }
+        };
+        match job_source::store_job(db, &job).await {
+            Ok(is_new) => {
+                report.imported += 1;
+                if is_new {
+                    report.new += 1;
+                }
+            }
+            Err(e) => report.errors.push(RowError {
+                row: row_no,
+                job_id: Some(job.job_id.clone()),
+                message: format!("DB error: {e}"),
+            }),
+        }
+    }
+    Ok(report)
+}
+
+/// Ubah isi file menjadi daftar baris (`Err` per baris jika tidak bisa dibaca sebagai job).
+pub fn parse_rows(bytes: &[u8], format: ImportFormat, csv_map: &str) -> Result<Vec<Result<Job, String>>, String> {
+    let text = std::str::from_utf8(bytes).map_err(|e| format!("file bukan UTF-8: {e}"))?;
+    let text = text.trim_start_matches('\u{FEFF}');
+
+    match format {
+        ImportFormat::Json => {
+            let value: Value = serde_json::from_str(text).map_err(|e| format!("JSON tidak valid: {e}"))?;
+            let items = match value {
+                Value::Array(items) => items,
+                Value::Object(ref obj) => match obj.get("data") {
+                    // response JSearch: { "status": .., "data": [ ... ] }
+                    Some(Value::Array(items)) => items.clone(),
+                    _ => vec![value],
+                },
+                _ => return Err("JSON harus berupa array job atau objek dengan field `data`".into()),
+            };
+            Ok(items.into_iter().map(job_from_value).collect())
+        }
+        ImportFormat::Ndjson => Ok(text
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str::<Value>(line)
+                    .map_err(|e| format!("JSON tidak valid: {e}"))
+                    .and_then(job_from_value)
+            })
+            .collect()),
+        ImportFormat::Csv => parse_csv(text, csv_map),
+    }
+}
+
+/// Objek berbentuk JSearch -> `Job`; objek asli disimpan sebagai `raw_json`.
+fn job_from_value(value: Value) -> Result<Job, String> {
+    let Value::Object(mut obj) = value.clone() else {
+        return Err("item bukan objek JSON".into());
+    };
+    // job_id boleh kosong (diisi saat validasi)
+    obj.entry("job_id").or_insert_with(|| Value::String(String::new()));
+    let mut job: Job = serde_json::from_value(Value::Object(obj)).map_err(|e| format!("field tidak cocok: {e}"))?;
+    if job.raw_json.is_none() {
+        job.raw_json = Some(value);
+    }
+    Ok(job)
+}
+
+// ==================== CSV ====================
+
+/// Alias header CSV umum -> nama field `Job`.
+const CSV_ALIASES: &[(&str, &str)] = &[
+    ("id", "job_id"),
+    ("title", "job_title"),
+    ("position", "job_title"),
+    ("company", "employer_name"),
+    ("employer", "employer_name"),
+    ("location", "job_location"),
+    ("city", "job_city"),
+    ("state", "job_state"),
+    ("country", "job_country"),
+    ("url", "job_apply_link"),
+    ("link", "job_apply_link"),
+    ("apply_link", "job_apply_link"),
+    ("apply_url", "job_apply_link"),
+    ("description", "job_description"),
+    ("publisher", "job_publisher"),
+    ("source", "job_publisher"),
+    ("employment_type", "job_employment_type"),
+    ("remote", "job_is_remote"),
+    ("is_remote", "job_is_remote"),
+    ("min_salary", "job_min_salary"),
+    ("max_salary", "job_max_salary"),
+    ("salary_min", "job_min_salary"),
+    ("salary_max", "job_max_salary"),
+    ("currency", "job_salary_currency"),
+    ("salary_currency", "job_salary_currency"),
+    ("salary_period", "job_salary_period"),
+    ("posted_at", "job_posted_at_datetime_utc"),
+    ("date_posted", "job_posted_at_datetime_utc"),
+    ("website", "employer_website"),
+];
+
+/// Field `Job` yang bisa diisi dari CSV.
+const CSV_FIELDS: &[&str] = &[
+    "job_id", "job_title", "employer_name", "employer_website", "job_location", "job_city",
+    "job_state", "job_country", "job_apply_link", "job_description", "job_publisher",
+    "job_employment_type", "job_is_remote", "job_min_salary", "job_max_salary",
+    "job_salary_currency", "job_salary_period", "job_posted_at_datetime_utc", "job_google_link",
+    "search_query",
+];
+
+/// Peta header -> field. `csv_map` (opsional) berformat `field=Header,field=Header`
+/// dan menimpa pemetaan otomatis (nama field / alias).
+fn csv_columns(headers: &csv::StringRecord, csv_map: &str) -> Result<HashMap<usize, &'static str>, String> {
+    let normalize = |s: &str| s.trim().to_lowercase().replace([' ', '-'], "_");
+    let mut columns = HashMap::new();
+
+    for (idx, header) in headers.iter().enumerate() {
+        let h = normalize(header);
+        let field = CSV_FIELDS
+            .iter()
+            .find(|f| **f == h)
+            .copied()
+            .or_else(|| CSV_ALIASES.iter().find(|(alias, _)| *alias == h).map(|(_, f)| *f));
+        if let Some(field) = field {
+            columns.insert(idx, field);
+        }
+    }
+
+    for pair in csv_map.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (field, header) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("mapping '{pair}' harus berformat field=Header"))?;
+        let field = CSV_FIELDS
+            .iter()
+            .find(|f| **f == field.trim())
+            .copied()
+            .ok_or_else(|| format!("field '{}' tidak dikenal", field.trim()))?;
+        let idx = headers
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(header.trim()))
+            .ok_or_else(|| format!("kolom '{}' tidak ada di header CSV", header.trim()))?;
+        columns.retain(|_, f| *f != field);
+        columns.insert(idx, field);
+    }
+
+    if !columns.values().any(|f| *f == "job_title") {
+        return Err("CSV tidak punya kolom judul (job_title / title); gunakan mapping".into());
+    }
+    Ok(columns)
+}
+
+fn parse_csv(text: &str, csv_map: &str) -> Result<Vec<Result<Job, String>>, String> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(text.as_bytes());
+    let headers = reader.headers().map_err(|e| format!("header CSV tidak valid: {e}"))?.clone();
+    let columns = csv_columns(&headers, csv_map)?;
+
+    Ok(reader
+        .records()
+        .map(|rec| {
+            let rec = rec.map_err(|e| format!("baris CSV tidak valid: {e}"))?;
+            let mut fields: Map<String, Value> = Map::new();
+            for (idx, value) in rec.iter().enumerate() {
+                let Some(field) = columns.get(&idx) else { continue };
+                let value = value.trim();
+                if !value.is_empty() {
+                    fields.insert((*field).to_string(), Value::String(value.to_string()));
+                }
+            }
+            job_from_csv(fields)
+        })
+        .collect())
+}
+
+fn csv_bool(s: &str) -> Result<bool, String> {
+    match s.to_lowercase().as_str() {
+        "1" | "true" | "yes" | "y" | "ya" | "remote" => Ok(true),
+        "0" | "false" | "no" | "n" | "tidak" | "onsite" | "on-site" => Ok(false),
+        _ => Err(format!("nilai boolean tidak valid: '{s}'")),
+    }
+}
+
+/// Angka gaji dari CSV: `120000`, `120000.50`, atau ribuan dengan koma (`120,000.50`).
+/// Akhiran (`120k`), simbol mata uang dan pemisah yang ambigu (`50.000` bisa berarti 50 atau
+/// 50 ribu; `1.234,56`) ditolak sebagai error baris, bukan ditebak.
+fn csv_decimal(field: &str, s: &str) -> Result<BigDecimal, String> {
+    let s = s.trim();
+    let invalid = |why: &str| format!("{field}: angka tidak valid '{s}' ({why})");
+
+    if let Some(c) = s.chars().find(|c| !(c.is_ascii_digit() || matches!(c, ',' | '.' | '-'))) {
+        return Err(invalid(&format!("karakter '{c}' tidak didukung; tulis angka penuh, mis. 120000")));
+    }
+    let (int_part, frac) = match s.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (s, None),
+    };
+    if frac.is_some_and(|f| f.contains(['.', ','])) {
+        return Err(invalid("pemisah ribuan titik / desimal koma tidak didukung; tulis tanpa pemisah ribuan"));
+    }
+    let digits = int_part.strip_prefix('-').unwrap_or(int_part);
+    if digits.contains(',') {
+        let mut groups = digits.split(',');
+        let first_ok = groups.next().is_some_and(|g| (1..=3).contains(&g.len()));
+        if !first_ok || !groups.all(|g| g.len() == 3) {
+            return Err(invalid("koma hanya boleh sebagai pemisah ribuan, mis. 120,000"));
+        }
+    } else if frac.is_some_and(|f| f.len() == 3) {
+        return Err(invalid("titik diikuti 3 digit ambigu (ribuan atau desimal?); tulis tanpa pemisah ribuan"));
+    }
+    BigDecimal::from_str(&s.replace(',', "")).map_err(|_| invalid("bukan angka"))
+}
+
+fn csv_datetime(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+        .ok_or_else(|| format!("tanggal tidak valid: '{s}' (pakai RFC3339 atau YYYY-MM-DD)"))
+}
+
+fn job_from_csv(fields: Map<String, Value>) -> Result<Job, String> {
+    let get = |k: &str| fields.get(k).and_then(|v| v.as_str()).map(str::to_string);
+
+    let posted = get("job_posted_at_datetime_utc").map(|s| csv_datetime(&s)).transpose()?;
+    let job = Job {
+        job_id: get("job_id").unwrap_or_default(),
+        search_query: get("search_query"),
+        employer_name: get("employer_name"),
+        employer_website: get("employer_website"),
+        job_publisher: get("job_publisher"),
+        job_employment_type: get("job_employment_type"),
+        job_title: get("job_title"),
+        job_apply_link: get("job_apply_link"),
+        job_description: get("job_description"),
+        job_is_remote: get("job_is_remote").map(|s| csv_bool(&s)).transpose()?,
+        job_location: get("job_location"),
+        job_city: get("job_city"),
+        job_state: get("job_state"),
+        job_country: get("job_country"),
+        job_google_link: get("job_google_link"),
+        job_min_salary: get("job_min_salary").map(|s| csv_decimal("job_min_salary", &s)).transpose()?,
+        job_max_salary: get("job_max_salary").map(|s| csv_decimal("job_max_salary", &s)).transpose()?,
+        job_salary_currency: get("job_salary_currency"),
+        job_salary_period: get("job_salary_period"),
+        job_posted_at_timestamp: posted.map(|d| d.timestamp()),
+        job_posted_at_datetime_utc: posted,
+        raw_json: Some(Value::Object(fields)),
+        ..Default::default()
+    };
+    Ok(job)
+}
+
+// ==================== Validasi ====================
+
+fn too_long(field: &str, value: Option<&str>, max: usize) -> Result<(), String> {
+    match value {
+        Some(v) if v.chars().count() > max => Err(format!("{field} lebih dari {max} karakter")),
+        _ => Ok(()),
+    }
+}
+
+/// Validasi sebelum upsert; job_id kosong diisi `import_<hash>`.
+fn validate(mut job: Job) -> Result<Job, String> {
+    let title = job.job_title.as_deref().map(str::trim).unwrap_or("");
+    if title.is_empty() {
+        return Err("job_title wajib diisi".into());
+    }
+    too_long("job_title", job.job_title.as_deref(), 512)?;
+    too_long("employer_name", job.employer_name.as_deref(), 255)?;
+    too_long("job_location", job.job_location.as_deref(), 255)?;
+    too_long("job_apply_link", job.job_apply_link.as_deref(), 1024)?;
+
+    if let Some(link) = job.job_apply_link.as_deref() {
+        if !(link.starts_with("http://") || link.starts_with("https://")) {
+            return Err(format!("job_apply_link bukan URL http(s): '{link}'"));
+        }
+    }
+    if let (Some(min), Some(max)) = (&job.job_min_salary, &job.job_max_salary) {
+        if min > max {
+            return Err("job_min_salary lebih besar dari job_max_salary".into());
+        }
+    }
+
+    job.job_id = job.job_id.trim().to_string();
+    if job.job_id.is_empty() {
+        let key = [&job.job_title, &job.employer_name, &job.job_location, &job.job_apply_link]
+            .iter()
+            .map(|f| f.as_deref().unwrap_or("").trim().to_lowercase())
+            .collect::<Vec<_>>()
+            .join("|");
+        let digest = Sha256::digest(key.as_bytes());
+        let hex: String = digest.iter().take(16).map(|b| format!("{b:02x}")).collect();
+        job.job_id = format!("{IMPORT_PREFIX}{hex}");
+    }
+    too_long("job_id", Some(&job.job_id), 64)?;
+
+    // link utama juga jadi opsi apply (JSON JSearch biasanya sudah punya apply_options)
+    if job.apply_options.is_empty() {
+        if let Some(link) = &job.job_apply_link {
+            job.apply_options.push(ApplyOption {
+                job_id: job.job_id.clone(),
+                publisher: job.job_publisher.clone(),
+                apply_link: Some(link.clone()),
+                ..Default::default()
+            });
+        }
+    }
+    Ok(job)
+}
+
+// ==================== CLI ====================
+
+/// `job_finder import <file> [--format json|ndjson|csv] [--map field=Header,..]`.
+/// Mengembalikan exit code (0 = semua baris tersimpan).
+pub async fn run_cli(db: &Database, args: &[String]) -> i32 {
+    let mut path: Option<&str> = None;
+    let mut format: Option<ImportFormat> = None;
+    let mut csv_map = String::new();
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--format" => match it.next().and_then(|f| ImportFormat::from_param(f)) {
+                Some(f) => format = Some(f),
+                None => {
+                    eprintln!("--format harus json, ndjson, atau csv");
+                    return 2;
+                }
+            },
+            "--map" => csv_map = it.next().cloned().unwrap_or_default(),
+            other if path.is_none() && !other.starts_with("--") => path = Some(other),
+            other => {
+                eprintln!("argumen tidak dikenal: {other}");
+                return 2;
+            }
+        }
+    }
+    let Some(path) = path else {
+        eprintln!("pemakaian: job_finder import <file> [--format json|ndjson|csv] [--map field=Header,..]");
+        return 2;
+    };
+
+    let bytes = match tokio::fs::read(path).await {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("gagal membaca {path}: {e}");
+            return 1;
+        }
+    };
+    let format = format.unwrap_or_else(|| ImportFormat::detect(path, &bytes));
+
+    match import_bytes(db, &bytes, format, &csv_map).await {
+        Ok(report) => {
+            for e in &report.errors {
+                match &e.job_id {
+                    Some(id) => eprintln!("baris {} ({id}): {}", e.row, e.message),
+                    None => eprintln!("baris {}: {}", e.row, e.message),
+                }
+            }
+            println!(
+                "import {path} ({}): {} baris, {} tersimpan ({} baru), {} error",
+                report.format,
+                report.total,
+                report.imported,
+                report.new,
+                report.errors.len()
+            );
+            if report.errors.is_empty() { 0 } else { 1 }
+        }
+        Err(e) => {
+            eprintln!("import {path} gagal: {e}");
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(names: &[&str]) -> csv::StringRecord {
+        csv::StringRecord::from(names.to_vec())
+    }
+
+    fn decimal(s: &str) -> BigDecimal {
+        BigDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn detect_by_extension_then_content() {
+        assert_eq!(ImportFormat::detect("jobs.CSV", b"[]"), ImportFormat::Csv);
+        assert_eq!(ImportFormat::detect("jobs.jsonl", b"[]"), ImportFormat::Ndjson);
+        assert_eq!(ImportFormat::detect("jobs.ndjson", b"title\n"), ImportFormat::Ndjson);
+
+        assert_eq!(ImportFormat::detect("upload", b"  [{\"job_title\": \"a\"}]"), ImportFormat::Json);
+        assert_eq!(ImportFormat::detect("upload", b"{\"a\": 1}\n{\"a\": 2}\n"), ImportFormat::Ndjson);
+        // satu objek (multi baris) -> JSON biasa
+        assert_eq!(ImportFormat::detect("upload", b"{\n  \"data\": []\n}"), ImportFormat::Json);
+        assert_eq!(ImportFormat::detect("upload", b"{\"data\": []}"), ImportFormat::Json);
+        assert_eq!(ImportFormat::detect("upload", "\u{FEFF}[]".as_bytes()), ImportFormat::Json);
+        assert_eq!(ImportFormat::detect("upload", b"title,company\nA,B"), ImportFormat::Csv);
+    }
+
+    #[test]
+    fn csv_columns_maps_fields_and_aliases() {
+        let cols = csv_columns(&headers(&["Title", "Company", "Apply URL", "unknown", "job_city"]), "").unwrap();
+        assert_eq!(cols.get(&0), Some(&"job_title"));
+        assert_eq!(cols.get(&1), Some(&"employer_name"));
+        assert_eq!(cols.get(&2), Some(&"job_apply_link"));
+        assert_eq!(cols.get(&3), None);
+        assert_eq!(cols.get(&4), Some(&"job_city"));
+    }
+
+    #[test]
+    fn csv_columns_map_overrides_automatic_mapping() {
+        let h = headers(&["title", "Role Name", "Where"]);
+        let cols = csv_columns(&h, "job_title=Role Name, job_location = where").unwrap();
+        assert_eq!(cols.get(&0), None);
+        assert_eq!(cols.get(&1), Some(&"job_title"));
+        assert_eq!(cols.get(&2), Some(&"job_location"));
+    }
+
+    #[test]
+    fn csv_columns_reports_bad_mapping() {
+        let h = headers(&["title", "company"]);
+        assert!(csv_columns(&h, "job_title").unwrap_err().contains("field=Header"));
+        assert!(csv_columns(&h, "salary=company").unwrap_err().contains("tidak dikenal"));
+        assert!(csv_columns(&h, "job_title=Role").unwrap_err().contains("tidak ada di header"));
+        assert!(csv_columns(&headers(&["company", "location"]), "").unwrap_err().contains("kolom judul"));
+    }
+
+    #[test]
+    fn csv_decimal_accepts_plain_and_comma_grouped_numbers() {
+        assert_eq!(csv_decimal("f", "120000").unwrap(), decimal("120000"));
+        assert_eq!(csv_decimal("f", " 120000.50 ").unwrap(), decimal("120000.50"));
+        assert_eq!(csv_decimal("f", "120,000").unwrap(), decimal("120000"));
+        assert_eq!(csv_decimal("f", "1,234,567.5").unwrap(), decimal("1234567.5"));
+        assert_eq!(csv_decimal("f", "45.5").unwrap(), decimal("45.5"));
+        assert_eq!(csv_decimal("f", "45.50").unwrap(), decimal("45.50"));
+    }
+
+    #[test]
+    fn csv_decimal_rejects_suffixes_and_ambiguous_separators() {
+        for bad in ["120k", "1.2m", "$90000", "Rp 5000000", "50.000", "15.000.000", "1.234,56", "50,5", "1,23,456", ""] {
+            let err = csv_decimal("job_min_salary", bad).unwrap_err();
+            assert!(err.starts_with("job_min_salary: angka tidak valid"), "{bad}: {err}");
+        }
+    }
+
+    #[test]
+    fn csv_row_error_instead_of_silent_value() {
+        let csv = "title,min_salary\nA,120k\nB,120000\n";
+        let rows = parse_rows(csv.as_bytes(), ImportFormat::Csv, "").unwrap();
+        assert!(rows[0].as_ref().unwrap_err().contains("120k"));
+        assert_eq!(rows[1].as_ref().unwrap().job_min_salary, Some(decimal("120000")));
+    }
+
+    #[test]
+    fn parses_example_csv_fixture() {
+        let rows = parse_rows(include_bytes!("../fixtures/import/example.csv"), ImportFormat::Csv, "").unwrap();
+        assert_eq!(rows.len(), 2);
+        let job = rows[0].as_ref().unwrap();
+        assert_eq!(job.job_title.as_deref(), Some("Senior Rust Engineer"));
+        assert_eq!(job.job_is_remote, Some(true));
+        assert_eq!(job.job_max_salary, Some(decimal("130000")));
+        assert_eq!(job.job_posted_at_datetime_utc.map(|d| d.to_rfc3339()).as_deref(), Some("2026-09-01T00:00:00+00:00"));
+        let job = rows[1].as_ref().unwrap();
+        assert_eq!(job.job_location.as_deref(), Some("Jakarta, Indonesia"));
+        assert_eq!(job.job_is_remote, Some(false));
+    }
+
+    fn job(title: &str) -> Job {
+        Job { job_title: Some(title.to_string()), ..Default::default() }
+    }
+
+    #[test]
+    fn validate_requires_title_and_http_link() {
+        assert_eq!(validate(job("  ")).unwrap_err(), "job_title wajib diisi");
+        let mut j = job("Engineer");
+        j.job_apply_link = Some("ftp://example.com/job".into());
+        assert!(validate(j).unwrap_err().contains("bukan URL http(s)"));
+        assert!(validate(job(&"x".repeat(513))).unwrap_err().contains("job_title lebih dari 512"));
+    }
+
+    #[test]
+    fn validate_rejects_min_above_max() {
+        let mut j = job("Engineer");
+        j.job_min_salary = Some(decimal("200"));
+        j.job_max_salary = Some(decimal("100"));
+        assert!(validate(j).unwrap_err().contains("lebih besar"));
+    }
+
+    #[test]
+    fn validate_fills_stable_import_id_and_apply_option() {
+        let mut j = job("Engineer");
+        j.employer_name = Some("Acme".into());
+        j.job_apply_link = Some("https://acme.example/jobs/1".into());
+        let a = validate(j.clone()).unwrap();
+        assert!(a.job_id.starts_with(IMPORT_PREFIX));
+        assert_eq!(a.job_id.len(), IMPORT_PREFIX.len() + 32);
+        // case/spasi tidak mengubah id
+        j.job_title = Some(" ENGINEER ".into());
+        assert_eq!(validate(j).unwrap().job_id, a.job_id);
+        assert_eq!(a.apply_options.len(), 1);
+        assert_eq!(a.apply_options[0].job_id, a.job_id);
+        assert_eq!(a.apply_options[0].apply_link.as_deref(), Some("https://acme.example/jobs/1"));
+
+        let mut j = job("Engineer");
+        j.job_id = "  given-id ".into();
+        assert_eq!(validate(j).unwrap().job_id, "given-id");
+    }
+}
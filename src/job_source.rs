@@ -0,0 +1,449 @@
+// src/job_source.rs
+//
+// Abstraksi sumber lowongan (JSearch, LinkedIn, dst). Handler, scheduler dan
+// penyimpanan cukup bekerja dengan `dyn JobSource` + `SearchRequest`, sehingga
+// menambah board baru = implementasi trait ini saja.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+
+use crate::database::Database;
+use crate::dedup;
+use crate::description;
+use crate::language;
+use crate::models::{Job, SavedSearch, SearchRun};
+use crate::salary;
+use crate::skills;
+use crate::usage::SoftLimitExceeded;
+use crate::workplace;
+
+/// Prefix job_id milik sumber selain JSearch (JSearch memakai id mentah dari API).
+pub const NON_JSEARCH_PREFIXES: &[&str] = &["li_", "feed_", "gh_", "lever_", "ashby_", "import_"];
+
+pub fn is_jsearch_job_id(job_id: &str) -> bool {
+    !NON_JSEARCH_PREFIXES.iter().any(|p| job_id.starts_with(p))
+}
+
+/// Prefix sumber dari sebuah job_id (`"li_"`, `"feed_"`, ...); `""` untuk JSearch.
+pub fn source_prefix(job_id: &str) -> &'static str {
+    NON_JSEARCH_PREFIXES.iter().copied().find(|p| job_id.starts_with(p)).unwrap_or("")
+}
+
+/// Posisi paging yang seragam untuk semua sumber.
+/// JSearch memakai nomor halaman, LinkedIn memakai `nextToken`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Cursor {
+    #[default]
+    Start,
+    Page(i32),
+    Token(String),
+}
+
+impl Cursor {
+    /// Bentuk string untuk disimpan di DB (search_cursors): `p:<n>` / `t:<token>`.
+    pub fn encode(&self) -> Option<String> {
+        match self {
+            Cursor::Start => None,
+            Cursor::Page(n) => Some(format!("p:{n}")),
+            Cursor::Token(t) => Some(format!("t:{t}")),
+        }
+    }
+
+    pub fn decode(s: &str) -> Cursor {
+        if let Some(n) = s.strip_prefix("p:").and_then(|n| n.parse().ok()) {
+            Cursor::Page(n)
+        } else if let Some(t) = s.strip_prefix("t:") {
+            Cursor::Token(t.to_string())
+        } else {
+            Cursor::Start
+        }
+    }
+}
+
+/// Parameter pencarian yang sudah dinormalisasi.
+/// Field yang tidak dipakai sebuah sumber cukup diabaikan oleh implementasinya.
+#[derive(Debug, Clone, Default)]
+pub struct SearchRequest {
+    pub query: String,
+    // JSearch
+    pub country: Option<String>,
+    pub language: Option<String>,
+    pub date_posted: Option<String>,
+    pub num_pages: i32,
+    // LinkedIn
+    pub location: Option<String>,
+    pub experience_levels: Option<String>,
+    pub workplace_types: Option<String>,
+    pub employment_types: Option<String>,
+    // paging
+    pub cursor: Cursor,
+}
+
+impl SearchRequest {
+    /// Semua parameter (termasuk cursor) sebagai JSON, untuk search_cache / search_runs.
+    pub fn params_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "query": self.query,
+            "country": self.country,
+            "language": self.language,
+            "date_posted": self.date_posted,
+            "num_pages": self.num_pages,
+            "location": self.location,
+            "experience_levels": self.experience_levels,
+            "workplace_types": self.workplace_types,
+            "employment_types": self.employment_types,
+            "cursor": self.cursor.encode(),
+        })
+    }
+
+    /// Kunci stabil untuk "query yang sama" (semua parameter kecuali cursor).
+    pub fn query_key(&self) -> String {
+        let parts = [
+            Some(self.query.trim()),
+            self.country.as_deref(),
+            self.language.as_deref(),
+            self.date_posted.as_deref(),
+            self.location.as_deref(),
+            self.experience_levels.as_deref(),
+            self.workplace_types.as_deref(),
+            self.employment_types.as_deref(),
+        ];
+        let mut key = parts.map(|p| p.unwrap_or("").trim().to_lowercase()).join("|");
+        key.push_str(&format!("|{}", self.num_pages.max(1)));
+        key
+    }
+}
+
+impl From<&SavedSearch> for SearchRequest {
+    fn from(s: &SavedSearch) -> Self {
+        Self {
+            query: s.query.clone(),
+            country: s.country.clone(),
+            language: s.language.clone(),
+            date_posted: s.date_posted.clone(),
+            num_pages: s.num_pages.max(1),
+            location: s.location.clone(),
+            experience_levels: s.experience_levels.clone(),
+            workplace_types: s.workplace_types.clone(),
+            employment_types: s.employment_types.clone(),
+            cursor: Cursor::Start,
+        }
+    }
+}
+
+/// Satu halaman hasil: job sudah dalam bentuk `Job` (siap upsert) + cursor berikutnya.
+#[derive(Debug, Clone, Default)]
+pub struct SearchPage {
+    pub jobs: Vec<Job>,
+    pub next: Option<Cursor>,
+}
+
+#[async_trait]
+pub trait JobSource: Send + Sync {
+    /// Nama pendek sumber (dipakai di notice / log).
+    fn name(&self) -> &'static str;
+
+    /// Host provider (untuk pencatatan kuota di api_calls).
+    fn host(&self) -> &str;
+
+    async fn search(&self, req: &SearchRequest) -> anyhow::Result<SearchPage>;
+
+    /// Apakah hasil search perlu dilengkapi lewat `enrich` (call tambahan per job).
+    fn enriches(&self) -> bool {
+        false
+    }
+
+    /// Maksimal enrichment yang berjalan bersamaan.
+    fn enrich_concurrency(&self) -> usize {
+        1
+    }
+
+    /// Lengkapi detail satu job (mis. deskripsi). Default: job dikembalikan apa adanya.
+    async fn enrich(&self, job: Job) -> anyhow::Result<Job> {
+        Ok(job)
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    Source(anyhow::Error),
+    Db(sqlx::Error),
+    /// soft limit harian RAPIDAPI_SOFT_LIMIT_DAILY tercapai (hanya dicek saat cache miss)
+    SoftLimit(String),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Source(e) => write!(f, "API error: {e}"),
+            FetchError::Db(e) => write!(f, "DB error saat menyimpan hasil: {e}"),
+            FetchError::SoftLimit(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FetchSummary {
+    pub pages: usize,
+    pub saved: usize,
+    /// semua job_id yang tersimpan pada fetch ini (baru + update), untuk search_run_jobs.
+    pub job_ids: Vec<String>,
+    /// job_id yang belum pernah ada di tabel jobs sebelum fetch ini.
+    pub new_job_ids: Vec<String>,
+    pub next: Option<Cursor>,
+    // enrichment (hanya untuk sumber dengan `enriches() == true`)
+    pub enriched: usize,
+    /// sudah ada di DB dengan deskripsi -> tidak perlu call detail
+    pub skipped: usize,
+    pub failed: usize,
+    /// paging berhenti karena soft limit harian tercapai di tengah jalan
+    pub limit_reached: Option<String>,
+}
+
+impl FetchSummary {
+    fn absorb(&mut self, page: FetchSummary) {
+        self.pages += page.pages;
+        self.saved += page.saved;
+        self.job_ids.extend(page.job_ids);
+        self.new_job_ids.extend(page.new_job_ids);
+        self.next = page.next;
+        self.enriched += page.enriched;
+        self.skipped += page.skipped;
+        self.failed += page.failed;
+    }
+}
+
+enum Enrichment {
+    NotNeeded,
+    Enriched,
+    Skipped,
+    Failed,
+}
+
+/// Simpan job + semua opsi apply-nya (dedup oleh uniq_job_link), lalu tautkan ke job
+/// kanonik jika posting yang sama sudah ada dari sumber lain (lihat `dedup`).
+/// Deskripsi dinormalisasi ke Markdown dulu (lihat `description`).
+/// Return `true` jika job baru pertama kali tersimpan.
+pub async fn store_job(db: &Database, job: &Job) -> Result<bool, sqlx::Error> {
+    // deskripsi dinormalisasi dulu, lalu bahasa dideteksi dari hasilnya (jika sumber tidak mengirim)
+    let job = &language::with_detected(&description::normalized(job));
+    let is_new = db.upsert_job(job).await?;
+
+    for opt in &job.apply_options {
+        if opt.apply_link.as_deref().map(|l| l.trim().is_empty()).unwrap_or(true) {
+            continue;
+        }
+        let mut opt = opt.clone();
+        opt.job_id = job.job_id.clone();
+        if let Err(e) = db.upsert_apply_option(&opt).await {
+            eprintln!("upsert_apply_option({}) error: {e}", job.job_id);
+        }
+    }
+
+    // gaji tahunan dalam SALARY_BASE_CURRENCY untuk filter/urutan /list; gagal tidak fatal
+    if let Err(e) = salary::store_annual(db, job).await {
+        eprintln!("salary annual({}) error: {e}", job.job_id);
+    }
+
+    // skill dari deskripsi + highlights -> job_skills; gagal tidak fatal
+    if let Err(e) = skills::store(db, job).await {
+        eprintln!("skills({}) error: {e}", job.job_id);
+    }
+
+    // remote/hybrid/onsite + wilayah remote; gagal tidak fatal
+    if let Err(e) = workplace::store(db, job).await {
+        eprintln!("workplace({}) error: {e}", job.job_id);
+    }
+
+    // duplikat lintas sumber -> tautkan ke job kanonik; gagal tidak fatal
+    if let Err(e) = dedup::link_job(db, job, dedup::min_similarity_from_env()).await {
+        eprintln!("dedup({}) error: {e}", job.job_id);
+    }
+    Ok(is_new)
+}
+
+/// Search satu halaman dari `source`, enrich job secara paralel (dibatasi
+/// `enrich_concurrency`), lalu simpan ke DB. Job yang sudah punya deskripsi di DB
+/// tidak di-enrich ulang dan hanya ditandai terlihat (`Database::mark_job_seen`).
+/// Gagal enrich tidak fatal (job dari hasil search tetap disimpan).
+pub async fn fetch_and_store(
+    source: &dyn JobSource,
+    req: &SearchRequest,
+    db: &Database,
+) -> Result<FetchSummary, FetchError> {
+    let page = source.search(req).await.map_err(|e| match e.downcast::<SoftLimitExceeded>() {
+        Ok(limit) => FetchError::SoftLimit(limit.0),
+        Err(e) => FetchError::Source(e),
+    })?;
+
+    let with_desc = if source.enriches() && !page.jobs.is_empty() {
+        let ids: Vec<String> = page.jobs.iter().map(|j| j.job_id.clone()).collect();
+        db.job_ids_with_description(&ids).await.map_err(FetchError::Db)?
+    } else {
+        Default::default()
+    };
+    let with_desc = &with_desc;
+
+    let results: Vec<(Job, Enrichment)> = stream::iter(page.jobs)
+        .map(|job| async move {
+            if !source.enriches() {
+                return (job, Enrichment::NotNeeded);
+            }
+            if with_desc.contains(&job.job_id) {
+                return (job, Enrichment::Skipped);
+            }
+            match source.enrich(job.clone()).await {
+                Ok(j) => (j, Enrichment::Enriched),
+                Err(e) => {
+                    eprintln!("{} enrich({}) error: {e}", source.name(), job.job_id);
+                    (job, Enrichment::Failed)
+                }
+            }
+        })
+        .buffer_unordered(source.enrich_concurrency().max(1))
+        .collect()
+        .await;
+
+    let mut summary = FetchSummary { pages: 1, next: page.next, ..Default::default() };
+    for (job, enrichment) in results {
+        match enrichment {
+            Enrichment::NotNeeded => {}
+            Enrichment::Enriched => summary.enriched += 1,
+            // baris di DB sudah lengkap; record search yang tipis tidak boleh menimpanya
+            Enrichment::Skipped => summary.skipped += 1,
+            Enrichment::Failed => summary.failed += 1,
+        }
+
+        if !matches!(enrichment, Enrichment::Skipped) && store_job(db, &job).await.map_err(FetchError::Db)? {
+            summary.new_job_ids.push(job.job_id.clone());
+        }
+        // hanya hasil search yang menggeser last_seen_at / mengaktifkan lagi job expired
+        db.mark_job_seen(&job.job_id, job.search_query.as_deref(), job.request_id.as_deref())
+            .await
+            .map_err(FetchError::Db)?;
+        summary.job_ids.push(job.job_id);
+        summary.saved += 1;
+    }
+
+    Ok(summary)
+}
+
+/// Seperti `fetch_and_store`, tapi mengikuti cursor `next` sampai `max_pages` halaman
+/// atau hasil habis. Error di halaman ke-2 dst tidak membatalkan halaman yang sudah
+/// tersimpan; `next` tetap menunjuk halaman yang gagal supaya bisa dilanjutkan.
+/// Soft limit (dari `CachedSource`, dicek per halaman yang tidak ada di cache) di halaman
+/// ke-2 dst juga menghentikan loop dengan `limit_reached` terisi.
+pub async fn fetch_and_store_pages(
+    source: &dyn JobSource,
+    req: &SearchRequest,
+    db: &Database,
+    max_pages: usize,
+) -> Result<FetchSummary, FetchError> {
+    let mut req = req.clone();
+    let mut total = FetchSummary::default();
+
+    for _ in 0..max_pages.max(1) {
+        let page = match fetch_and_store(source, &req, db).await {
+            Ok(p) => p,
+            Err(FetchError::SoftLimit(msg)) if total.pages > 0 => {
+                eprintln!("{} page {}: {msg} (berhenti)", source.name(), total.pages + 1);
+                total.next = Some(req.cursor.clone());
+                total.limit_reached = Some(msg);
+                break;
+            }
+            Err(e) if total.pages > 0 => {
+                eprintln!("{} page {} error (berhenti): {e}", source.name(), total.pages + 1);
+                total.next = Some(req.cursor.clone());
+                break;
+            }
+            Err(e) => return Err(e),
+        };
+
+        let next = page.next.clone().filter(|_| page.saved > 0);
+        total.absorb(page);
+
+        match next {
+            Some(next) => req.cursor = next,
+            None => break,
+        }
+    }
+
+    Ok(total)
+}
+
+/// Asal sebuah run di search_runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOrigin {
+    /// form fetch di UI
+    Manual,
+    /// scheduler saved search (id saved search)
+    Scheduler(u64),
+    /// poller feed background
+    Poller,
+}
+
+impl RunOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunOrigin::Manual => "manual",
+            RunOrigin::Scheduler(_) => "scheduler",
+            RunOrigin::Poller => "poller",
+        }
+    }
+
+    pub fn saved_search_id(&self) -> Option<u64> {
+        match self {
+            RunOrigin::Scheduler(id) => Some(*id),
+            _ => None,
+        }
+    }
+}
+
+/// Catat satu fetch ke search_runs (+ search_run_jobs). Gagal mencatat hanya di-log.
+/// Return id run jika berhasil.
+pub async fn record_run(
+    db: &Database,
+    source: &str,
+    origin: RunOrigin,
+    req: &SearchRequest,
+    started_at: DateTime<Utc>,
+    outcome: Result<&FetchSummary, &str>,
+    cached_pages: usize,
+) -> Option<u64> {
+    let (summary, error) = match outcome {
+        Ok(s) => (Some(s), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let saved = summary.map(|s| s.saved).unwrap_or(0);
+    let new = summary.map(|s| s.new_job_ids.len()).unwrap_or(0);
+
+    let run = SearchRun {
+        id: 0,
+        source: source.to_string(),
+        origin: origin.as_str().to_string(),
+        saved_search_id: origin.saved_search_id(),
+        query: req.query.trim().chars().take(255).collect(),
+        params_json: Some(req.params_json()),
+        started_at,
+        finished_at: Utc::now(),
+        pages: summary.map(|s| s.pages).unwrap_or(0) as i32,
+        result_count: saved as i32,
+        new_count: new as i32,
+        updated_count: saved.saturating_sub(new) as i32,
+        cached_pages: cached_pages as i32,
+        error,
+    };
+    let (job_ids, new_job_ids) = match summary {
+        Some(s) => (s.job_ids.as_slice(), s.new_job_ids.as_slice()),
+        None => (&[][..], &[][..]),
+    };
+
+    match db.record_search_run(&run, job_ids, new_job_ids).await {
+        Ok(id) => Some(id),
+        Err(e) => {
+            eprintln!("record_search_run({source}) error: {e}");
+            None
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// src/language.rs
+//
+// Deteksi bahasa posting secara offline (whatlang, trigram) dari judul + deskripsi.
+// JSearch mengisi `job_posting_language` sendiri; LinkedIn, feed, ATS dan import tidak.
+// Saat upsert, kolom yang kosong diisi kode ISO 639-1 ("en", "id", ...), format yang sama
+// dengan JSearch, supaya cover letter (`OpenAIClient::generate_cover_letter`) dan filter
+// bahasa di /list bekerja untuk semua sumber. Catatan: whatlang tidak membedakan bahasa
+// Melayu; posting Melayu terdeteksi sebagai "id".
+
+use whatlang::Lang;
+
+use crate::database::Database;
+use crate::models::Job;
+
+/// Teks lebih pendek dari ini (mis. hanya judul) tidak dideteksi.
+const MIN_TEXT_CHARS: usize = 40;
+
+/// Confidence whatlang minimal. `Info::is_reliable` terlalu ketat untuk posting pendek
+/// (teks Inggris 100 karakter sering < 0.9).
+const MIN_CONFIDENCE: f64 = 0.5;
+
+/// Karakter deskripsi yang dipakai; cukup untuk deteksi, hemat CPU untuk deskripsi panjang.
+const MAX_TEXT_CHARS: usize = 2000;
+
+/// ISO 639-3 (whatlang) -> ISO 639-1. Bahasa lain disimpan dengan kode 639-3.
+const ISO_639_1: &[(Lang, &str)] = &[
+    (Lang::Eng, "en"),
+    (Lang::Ind, "id"),
+    (Lang::Deu, "de"),
+    (Lang::Fra, "fr"),
+    (Lang::Spa, "es"),
+    (Lang::Por, "pt"),
+    (Lang::Ita, "it"),
+    (Lang::Nld, "nl"),
+    (Lang::Pol, "pl"),
+    (Lang::Ces, "cs"),
+    (Lang::Slk, "sk"),
+    (Lang::Hun, "hu"),
+    (Lang::Ron, "ro"),
+    (Lang::Bul, "bg"),
+    (Lang::Hrv, "hr"),
+    (Lang::Srp, "sr"),
+    (Lang::Slv, "sl"),
+    (Lang::Ell, "el"),
+    (Lang::Swe, "sv"),
+    (Lang::Dan, "da"),
+    (Lang::Nob, "no"),
+    (Lang::Fin, "fi"),
+    (Lang::Est, "et"),
+    (Lang::Lav, "lv"),
+    (Lang::Lit, "lt"),
+    (Lang::Rus, "ru"),
+    (Lang::Ukr, "uk"),
+    (Lang::Tur, "tr"),
+    (Lang::Ara, "ar"),
+    (Lang::Heb, "he"),
+    (Lang::Pes, "fa"),
+    (Lang::Hin, "hi"),
+    (Lang::Ben, "bn"),
+    (Lang::Urd, "ur"),
+    (Lang::Tam, "ta"),
+    (Lang::Jpn, "ja"),
+    (Lang::Kor, "ko"),
+    (Lang::Cmn, "zh"),
+    (Lang::Vie, "vi"),
+    (Lang::Tha, "th"),
+    (Lang::Tgl, "tl"),
+    (Lang::Jav, "jv"),
+    (Lang::Cat, "ca"),
+    (Lang::Afr, "af"),
+];
+
+fn code_of(lang: Lang) -> &'static str {
+    ISO_639_1
+        .iter()
+        .find(|(l, _)| *l == lang)
+        .map(|(_, c)| *c)
+        .unwrap_or_else(|| lang.code())
+}
+
+/// Nama bahasa dalam bahasa Inggris untuk kode yang disimpan ("id" -> "Indonesian").
+pub fn display_name(code: &str) -> String {
+    let lang = ISO_639_1
+        .iter()
+        .find(|(_, c)| c.eq_ignore_ascii_case(code))
+        .map(|(l, _)| *l)
+        .or_else(|| Lang::from_code(code.to_lowercase()));
+    match lang {
+        Some(l) => l.eng_name().to_string(),
+        None => code.to_string(),
+    }
+}
+
+/// Kode bahasa `text`; `None` jika terlalu pendek atau confidence whatlang rendah.
+pub fn detect(text: &str) -> Option<&'static str> {
+    let text: String = text.chars().take(MAX_TEXT_CHARS).collect();
+    if text.chars().filter(|c| c.is_alphabetic()).count() < MIN_TEXT_CHARS {
+        return None;
+    }
+    let info = whatlang::detect(&text)?;
+    (info.confidence() >= MIN_CONFIDENCE).then(|| code_of(info.lang()))
+}
+
+/// Judul + deskripsi job.
+pub fn detect_job(job: &Job) -> Option<&'static str> {
+    let text = format!(
+        "{}\n{}",
+        job.job_title.as_deref().unwrap_or(""),
+        job.job_description.as_deref().unwrap_or("")
+    );
+    detect(&text)
+}
+
+/// Salinan `job` dengan `job_posting_language` terisi jika sumber tidak mengirimnya.
+pub fn with_detected(job: &Job) -> Job {
+    let mut job = job.clone();
+    if job.job_posting_language.as_deref().map(|l| l.trim().is_empty()).unwrap_or(true) {
+        job.job_posting_language = detect_job(&job).map(str::to_string);
+    }
+    job
+}
+
+/// `job_finder language`: deteksi bahasa job lama yang `job_posting_language`-nya kosong.
+/// Mengembalikan exit code.
+pub async fn run_cli(db: &Database) -> i32 {
+    let job_ids = match db.job_ids_without_language().await {
+        Ok(ids) => ids,
+        Err(e) => {
+            eprintln!("language error: {e}");
+            return 1;
+        }
+    };
+
+    let (mut detected, mut unknown) = (0, 0);
+    for job_id in &job_ids {
+        let job = match db.find_job(job_id).await {
+            Ok(Some(job)) => job,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("language error: {e}");
+                return 1;
+            }
+        };
+        let Some(code) = detect_job(&job) else {
+            unknown += 1;
+            continue;
+        };
+        if let Err(e) = db.set_job_language(job_id, code).await {
+            eprintln!("language error: {e}");
+            return 1;
+        }
+        detected += 1;
+    }
+    println!(
+        "language: {} job tanpa bahasa, {detected} terdeteksi, {unknown} tidak pasti",
+        job_ids.len()
+    );
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN: &str = "We are looking for a backend engineer to build and operate our payment \
+        services. You will work closely with product and design teams.";
+    const ID: &str = "Kami mencari seorang pengembang backend untuk membangun dan mengelola \
+        layanan pembayaran kami. Anda akan bekerja sama dengan tim produk dan desain.";
+
+    #[test]
+    fn detect_table() {
+        for (text, expected) in [
+            ("", None),
+            ("Backend Engineer", None),
+            ("Senior Rust Engineer (Remote)", None),
+            (EN, Some("en")),
+            (ID, Some("id")),
+        ] {
+            assert_eq!(detect(text), expected, "{text}");
+        }
+    }
+
+    #[test]
+    fn code_of_falls_back_to_639_3() {
+        assert_eq!(code_of(Lang::Eng), "en");
+        assert_eq!(code_of(Lang::Ind), "id");
+        assert_eq!(code_of(Lang::Epo), "epo");
+    }
+
+    #[test]
+    fn display_name_table() {
+        for (code, expected) in [("en", "English"), ("ID", "Indonesian"), ("epo", "Esperanto"), ("xx", "xx")] {
+            assert_eq!(display_name(code), expected, "{code}");
+        }
+    }
+
+    #[test]
+    fn with_detected_keeps_provider_language() {
+        let job = Job {
+            job_title: Some("Backend Engineer".into()),
+            job_description: Some(ID.into()),
+            job_posting_language: Some("en".into()),
+            ..Default::default()
+        };
+        assert_eq!(with_detected(&job).job_posting_language.as_deref(), Some("en"));
+
+        let blank = Job { job_posting_language: Some(" ".into()), ..job.clone() };
+        assert_eq!(with_detected(&blank).job_posting_language.as_deref(), Some("id"));
+
+        let missing = Job { job_posting_language: None, job_description: None, ..job };
+        assert_eq!(with_detected(&missing).job_posting_language, None);
+    }
+}
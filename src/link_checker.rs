@@ -0,0 +1,340 @@
+// src/link_checker.rs
+//
+// Cek liveness link apply di job_apply_options. Request HEAD dikirim dulu; jika ditolak
+// (405/501, atau 403 dari server yang memblokir HEAD), dicoba GET. Redirect diikuti.
+// Hasil per opsi disimpan: status code, URL akhir, waktu cek, dan `link_alive`:
+//   1    = 2xx/3xx
+//   0    = 404/410, atau host tidak bisa dihubungi (DNS / koneksi ditolak) pada
+//          `LINK_CHECK_DEAD_AFTER` cek berturut-turut (`link_fail_count`)
+//   NULL = tidak pasti (401/403/429/5xx, timeout, proteksi bot), jadi tidak dianggap mati
+// Job yang semua opsi apply-nya mati ditandai di /list dan halaman detail.
+//
+// Link berasal dari data pihak ketiga, jadi hanya alamat publik yang dihubungi: host
+// loopback / privat / link-local (mis. 169.254.169.254) ditolak, termasuk hasil DNS dan
+// tujuan redirect.
+//
+// `LINK_CHECK_MINUTES` (default 360; 0 = background nonaktif), `LINK_CHECK_BATCH` (100),
+// `LINK_CHECK_CONCURRENCY` (8), `LINK_CHECK_RECHECK_HOURS` (24), `LINK_CHECK_TIMEOUT_SECS` (15),
+// `LINK_CHECK_DEAD_AFTER` (3).
+
+use std::env;
+use std::error::Error as _;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{Client, StatusCode, Url};
+
+use crate::database::Database;
+
+fn env_u64(key: &str, default: u64) -> u64 {
+    env::var(key).ok().and_then(|s| s.trim().parse().ok()).unwrap_or(default)
+}
+
+/// Hasil cek satu link.
+#[derive(Debug, Clone, Default)]
+pub struct LinkStatus {
+    pub status: Option<u16>,
+    pub final_url: Option<String>,
+    pub alive: Option<bool>,
+    pub error: Option<String>,
+    /// host tidak bisa dihubungi; baru dianggap mati setelah beberapa kali berturut-turut
+    pub unreachable: bool,
+}
+
+/// Host yang ditolak karena bukan alamat publik.
+#[derive(Debug)]
+struct BlockedHost(String);
+
+impl std::fmt::Display for BlockedHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "alamat internal ditolak: {}", self.0)
+    }
+}
+
+impl std::error::Error for BlockedHost {}
+
+/// `false` untuk loopback, privat, link-local, CGNAT, multicast, dokumentasi, dsb.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || a == 0
+                || a >= 240
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 192 && b == 0 && v4.octets()[2] == 0)
+                || (a == 198 && (b == 18 || b == 19)))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+                || (first == 0x2001 && v6.segments()[1] == 0x0db8))
+        }
+    }
+}
+
+/// Tolak URL yang jelas menuju host internal tanpa perlu DNS (IP literal, `localhost`).
+fn blocked_host(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    // IPv6 literal ditulis dalam kurung siku
+    let literal = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = literal.parse::<IpAddr>() {
+        return (!is_public_ip(ip)).then(|| ip.to_string());
+    }
+    let domain = host.trim_end_matches('.').to_ascii_lowercase();
+    (domain == "localhost" || domain.ends_with(".localhost")).then_some(domain)
+}
+
+/// Resolver yang gagal jika salah satu alamat hasil DNS bukan alamat publik.
+struct PublicOnlyResolver;
+
+impl Resolve for PublicOnlyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if addrs.iter().any(|a| !is_public_ip(a.ip())) {
+                return Err(Box::new(BlockedHost(host)) as Box<dyn std::error::Error + Send + Sync>);
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Error request disebabkan `BlockedHost` (dari resolver atau policy redirect)?
+fn is_blocked(e: &reqwest::Error) -> bool {
+    let mut source = e.source();
+    while let Some(s) = source {
+        if s.is::<BlockedHost>() {
+            return true;
+        }
+        source = s.source();
+    }
+    false
+}
+
+#[derive(Debug, Default)]
+pub struct CheckSummary {
+    pub checked: usize,
+    pub alive: usize,
+    pub dead: usize,
+    pub unknown: usize,
+}
+
+#[derive(Clone)]
+pub struct LinkChecker {
+    client: Client,
+    concurrency: usize,
+    recheck_hours: i64,
+    dead_after: u32,
+}
+
+impl LinkChecker {
+    pub fn from_env() -> Self {
+        let timeout = env_u64("LINK_CHECK_TIMEOUT_SECS", 15).max(1);
+        // maksimal 10 redirect, dan tidak ke IP internal (nama host dicek oleh resolver)
+        let redirect = reqwest::redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= 10 {
+                attempt.error("terlalu banyak redirect")
+            } else if let Some(host) = blocked_host(attempt.url()) {
+                attempt.error(BlockedHost(host))
+            } else {
+                attempt.follow()
+            }
+        });
+        Self {
+            client: Client::builder()
+                .user_agent("Mozilla/5.0 (compatible; job-finder-link-checker/0.1)")
+                .timeout(Duration::from_secs(timeout))
+                .redirect(redirect)
+                .dns_resolver(Arc::new(PublicOnlyResolver))
+                .build()
+                .expect("reqwest client"),
+            concurrency: env_u64("LINK_CHECK_CONCURRENCY", 8).clamp(1, 64) as usize,
+            recheck_hours: env_u64("LINK_CHECK_RECHECK_HOURS", 24) as i64,
+            dead_after: env_u64("LINK_CHECK_DEAD_AFTER", 3).clamp(1, 100) as u32,
+        }
+    }
+
+    /// HEAD, fallback GET jika server tidak mendukung / menolak HEAD.
+    /// Hanya URL http(s) ke alamat publik yang dihubungi.
+    pub async fn check(&self, url: &str) -> LinkStatus {
+        let parsed = match Url::parse(url) {
+            Ok(u) if matches!(u.scheme(), "http" | "https") => u,
+            _ => return LinkStatus { error: Some("bukan URL http(s)".into()), ..Default::default() },
+        };
+        if let Some(host) = blocked_host(&parsed) {
+            return LinkStatus { error: Some(BlockedHost(host).to_string()), ..Default::default() };
+        }
+
+        let head = self.client.head(url).send().await;
+        let resp = match head {
+            Ok(r) if !matches!(
+                r.status(),
+                StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED | StatusCode::FORBIDDEN
+            ) =>
+            {
+                Ok(r)
+            }
+            // GET juga dipakai saat HEAD gagal di level koneksi/timeout (sebagian server memutus HEAD)
+            _ => self.client.get(url).send().await,
+        };
+
+        match resp {
+            Ok(r) => {
+                let status = r.status();
+                LinkStatus {
+                    status: Some(status.as_u16()),
+                    final_url: Some(r.url().to_string()).filter(|u| u != url),
+                    alive: classify(status),
+                    error: None,
+                    unreachable: false,
+                }
+            }
+            Err(e) => {
+                let blocked = is_blocked(&e);
+                let error = if blocked { "alamat internal ditolak".to_string() } else { e.to_string() };
+                LinkStatus {
+                    error: Some(error.chars().take(255).collect()),
+                    unreachable: e.is_connect() && !blocked,
+                    ..Default::default()
+                }
+            }
+        }
+    }
+
+    /// Cek maksimal `limit` opsi apply yang belum pernah dicek atau terakhir dicek
+    /// lebih dari `LINK_CHECK_RECHECK_HOURS` jam lalu.
+    pub async fn run_batch(&self, db: &Database, limit: i64) -> Result<CheckSummary, sqlx::Error> {
+        let older_than = Utc::now() - chrono::Duration::hours(self.recheck_hours);
+        let due = db.apply_options_due_for_check(older_than, limit).await?;
+
+        let results: Vec<(u64, LinkStatus)> = stream::iter(due)
+            .map(|(id, link)| async move { (id, self.check(&link).await) })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut summary = CheckSummary::default();
+        for (id, status) in results {
+            let alive = db.record_link_check(id, &status, self.dead_after).await?;
+            summary.checked += 1;
+            match alive {
+                Some(true) => summary.alive += 1,
+                Some(false) => summary.dead += 1,
+                None => summary.unknown += 1,
+            }
+        }
+        Ok(summary)
+    }
+}
+
+/// 2xx/3xx hidup, 404/410 mati, selain itu tidak pasti.
+fn classify(status: StatusCode) -> Option<bool> {
+    if status.is_success() || status.is_redirection() {
+        Some(true)
+    } else if status == StatusCode::NOT_FOUND || status == StatusCode::GONE {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Jalankan checker di task Tokio terpisah tiap `LINK_CHECK_MINUTES`.
+pub fn spawn(db: Database, checker: LinkChecker) {
+    let minutes = env_u64("LINK_CHECK_MINUTES", 360);
+    if minutes == 0 {
+        println!("Link checker disabled (LINK_CHECK_MINUTES=0)");
+        return;
+    }
+    let batch = env_u64("LINK_CHECK_BATCH", 100).max(1) as i64;
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(minutes * 60));
+        loop {
+            ticker.tick().await;
+            match checker.run_batch(&db, batch).await {
+                Ok(s) if s.checked > 0 => println!(
+                    "link checker: {} dicek, {} hidup, {} mati, {} tidak pasti",
+                    s.checked, s.alive, s.dead, s.unknown
+                ),
+                Ok(_) => {}
+                Err(e) => eprintln!("link checker error: {e}"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocked(url: &str) -> bool {
+        blocked_host(&Url::parse(url).unwrap()).is_some()
+    }
+
+    #[test]
+    fn rejects_internal_hosts() {
+        for url in [
+            "http://169.254.169.254/latest/meta-data/",
+            "http://127.0.0.1:8080/",
+            "http://10.0.0.5/jobs",
+            "http://192.168.1.1/",
+            "http://172.16.0.1/",
+            "http://100.64.0.1/",
+            "http://0.0.0.0/",
+            "http://[::1]/",
+            "http://[fe80::1]/",
+            "http://[fd00::1]/",
+            "http://[::ffff:127.0.0.1]/",
+            "http://localhost:3000/",
+            "http://api.localhost/",
+        ] {
+            assert!(blocked(url), "{url}");
+        }
+        for url in ["https://example.com/jobs/1", "http://93.184.216.34/", "http://[2606:4700::1111]/"] {
+            assert!(!blocked(url), "{url}");
+        }
+    }
+
+    #[tokio::test]
+    async fn check_does_not_contact_internal_or_non_http_urls() {
+        let checker = LinkChecker::from_env();
+        let status = checker.check("http://169.254.169.254/latest/meta-data/").await;
+        assert_eq!(status.alive, None);
+        assert!(!status.unreachable);
+        assert!(status.error.unwrap().contains("alamat internal ditolak"));
+
+        let status = checker.check("file:///etc/passwd").await;
+        assert_eq!(status.alive, None);
+        assert_eq!(status.error.as_deref(), Some("bukan URL http(s)"));
+    }
+
+    #[test]
+    fn classifies_status_codes() {
+        assert_eq!(classify(StatusCode::OK), Some(true));
+        assert_eq!(classify(StatusCode::MOVED_PERMANENTLY), Some(true));
+        assert_eq!(classify(StatusCode::NOT_FOUND), Some(false));
+        assert_eq!(classify(StatusCode::GONE), Some(false));
+        assert_eq!(classify(StatusCode::FORBIDDEN), None);
+        assert_eq!(classify(StatusCode::TOO_MANY_REQUESTS), None);
+        assert_eq!(classify(StatusCode::SERVICE_UNAVAILABLE), None);
+    }
+}
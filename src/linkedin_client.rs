@@ -1,7 +1,16 @@
+use std::str::FromStr;
 use std::time::Duration;
 use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde_json::Value;
+use sqlx::types::BigDecimal;
+
+use crate::job_source::{Cursor, JobSource, SearchPage, SearchRequest};
+use crate::models::{ApplyOption, Job};
+use crate::cassette::Cassette;
+use crate::retry::{Retrier, RetryPolicy};
+use crate::usage::UsageTracker;
 
 #[derive(Clone)]
 pub struct LinkedInApiClient {
@@ -9,6 +18,9 @@ pub struct LinkedInApiClient {
     base_url: String,
     host: String,
     client: reqwest::Client,
+    retry: Retrier,
+    cassette: Cassette,
+    enrich_concurrency: usize,
 }
 
 impl LinkedInApiClient {
@@ -24,12 +36,44 @@ impl LinkedInApiClient {
             base_url: "https://jobs-api14.p.rapidapi.com/v2/linkedin".to_string(),
             host: "jobs-api14.p.rapidapi.com".to_string(),
             client,
+            retry: Retrier::new(RetryPolicy::default()),
+            cassette: Cassette::default(),
+            enrich_concurrency: 4,
         }
     }
 
+    /// Ganti base URL `.../v2/linkedin` (`LINKEDIN_BASE_URL`, mock).
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Rekam / putar ulang response lewat cassette (`HTTP_CASSETTE`).
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    /// Jumlah call /get yang boleh berjalan bersamaan saat enrichment.
+    pub fn with_enrich_concurrency(mut self, n: usize) -> Self {
+        self.enrich_concurrency = n.max(1);
+        self
+    }
+
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Retrier::new(policy);
+        self
+    }
+
+    /// Catat semua call keluar ke api_calls (panggil setelah `with_retry`).
+    pub fn with_usage(mut self, usage: UsageTracker) -> Self {
+        self.retry = self.retry.with_usage(usage, &self.host);
+        self
+    }
+
     fn auth_headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
-        headers.insert("x-rapidapi-host", HeaderValue::from_static("jobs-api14.p.rapidapi.com"));
+        headers.insert("x-rapidapi-host", HeaderValue::from_str(&self.host).expect("invalid rapidapi host"));
         headers.insert("x-rapidapi-key", HeaderValue::from_str(&self.api_key).expect("invalid rapidapi key"));
         headers
     }
@@ -42,17 +86,17 @@ impl LinkedInApiClient {
     }
 
     async fn send_json_with_detail(&self, req: reqwest::RequestBuilder) -> Result<Value> {
-        let resp = req.send().await.context("request failed")?;
-        let status = resp.status();
-        let text = resp.text().await.unwrap_or_default();
+        let exchange = self.cassette.send("linkedin", req, Some(&self.retry)).await?;
+        let status = exchange.status;
+        let text = &exchange.body;
 
         if !status.is_success() {
             // <- di sinilah body error ikut dinaikkan ke caller
             bail!("HTTP {}: {}", status, text);
         }
 
-        let json: Value = serde_json::from_str(&text)
-            .with_context(|| format!("invalid JSON: {}", text))?;
+        let json: Value = serde_json::from_str(text)
+            .with_context(|| format!("invalid JSON: {}{}", text, exchange.cassette_hint()))?;
         Ok(json)
     }
 
@@ -89,3 +133,404 @@ impl LinkedInApiClient {
         self.send_json_with_detail(req).await
     }
 }
+
+/// String pertama yang tidak kosong dari beberapa path (mis. "companyName" atau "company.name").
+/// Angka dan boolean dijadikan teks ("42", "true"); array diwakili elemen pertama yang
+/// bisa dibaca (mis. `workplaceTypes: ["remote"]`).
+pub fn first_str(obj: &Value, paths: &[&str]) -> Option<String> {
+    paths.iter().find_map(|path| {
+        let mut cur = obj;
+        for key in path.split('.') {
+            cur = cur.get(key)?;
+        }
+        scalar_str(cur)
+    })
+}
+
+fn scalar_str(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Array(items) => items.iter().find_map(scalar_str),
+        _ => None,
+    }
+}
+
+fn decimal_of(v: &Value) -> Option<BigDecimal> {
+    match v {
+        Value::Number(n) => BigDecimal::from_str(&n.to_string()).ok(),
+        Value::String(s) => parse_amount(s),
+        _ => None,
+    }
+}
+
+/// "120,000" / "120k" / "45.50" / "€50.000" / "Rp 15.000.000" / "1.234,56" -> angka.
+/// `.` atau `,` yang diikuti tepat 3 digit adalah pemisah ribuan; hanya pemisah terakhir
+/// (jenisnya beda dari pemisah sebelumnya) yang boleh jadi titik desimal. Selain itu -> None.
+fn parse_amount(s: &str) -> Option<BigDecimal> {
+    let s = s.trim().to_lowercase();
+    let (num, mult) = match s.strip_suffix('k') {
+        Some(n) => (n, 1000),
+        None => (s.as_str(), 1),
+    };
+    let cleaned: String = num.chars().filter(|c| c.is_ascii_digit() || matches!(c, '.' | ',')).collect();
+    let groups: Vec<&str> = cleaned.split(['.', ',']).collect();
+    let seps: Vec<char> = cleaned.chars().filter(|c| matches!(c, '.' | ',')).collect();
+    if groups.iter().any(|g| g.is_empty()) {
+        return None;
+    }
+
+    let last = groups.len() - 1;
+    let decimal = last > 0 && groups[last].len() != 3;
+    let thousands = if decimal { &seps[..last - 1] } else { &seps[..] };
+    if decimal && thousands.contains(&seps[last - 1]) {
+        return None;
+    }
+    if thousands.windows(2).any(|w| w[0] != w[1]) {
+        return None;
+    }
+    let int_end = if decimal { last } else { last + 1 };
+    if int_end > 1 && (groups[0].len() > 3 || groups[1..int_end].iter().any(|g| g.len() != 3)) {
+        return None;
+    }
+
+    let mut plain = groups[..int_end].concat();
+    if decimal {
+        plain.push('.');
+        plain.push_str(groups[last]);
+    }
+    BigDecimal::from_str(&plain).ok().map(|d| d * BigDecimal::from(mult))
+}
+
+/// Normalisasi periode gaji ke gaya JSearch (YEAR / MONTH / WEEK / HOUR).
+fn salary_period(s: &str) -> Option<String> {
+    let s = s.to_lowercase();
+    let p = if s.contains("hour") || s.contains("/hr") || s == "hr" {
+        "HOUR"
+    } else if s.contains("month") || s.contains("/mo") || s == "mo" {
+        "MONTH"
+    } else if s.contains("week") || s.contains("/wk") {
+        "WEEK"
+    } else if s.contains("year") || s.contains("annual") || s.contains("/yr") || s == "yr" {
+        "YEAR"
+    } else {
+        return None;
+    };
+    Some(p.to_string())
+}
+
+fn currency_of_text(s: &str) -> Option<String> {
+    let symbols = [("$", "USD"), ("€", "EUR"), ("£", "GBP"), ("¥", "JPY"), ("₹", "INR"), ("Rp", "IDR")];
+    symbols
+        .iter()
+        .find(|(sym, _)| s.contains(sym))
+        .map(|(_, code)| code.to_string())
+        .or_else(|| {
+            s.split(|c: char| !c.is_ascii_alphabetic())
+                .find(|w| w.len() == 3 && w.chars().all(|c| c.is_ascii_uppercase()))
+                .map(|w| w.to_string())
+        })
+}
+
+struct Salary {
+    min: Option<BigDecimal>,
+    max: Option<BigDecimal>,
+    currency: Option<String>,
+    period: Option<String>,
+}
+
+/// Gaji bisa berupa object `{min, max, currency, period}` atau teks
+/// seperti "$120,000/yr - $150,000/yr".
+fn salary_of(obj: &Value) -> Option<Salary> {
+    let v = ["salary", "salaryRange", "compensation"].iter().find_map(|k| obj.get(*k))?;
+
+    match v {
+        Value::Object(_) => {
+            let num = |keys: &[&str]| keys.iter().find_map(|k| v.get(*k).and_then(decimal_of));
+            let min = num(&["min", "minAmount", "from", "minimum"]);
+            let max = num(&["max", "maxAmount", "to", "maximum"]);
+            let currency = first_str(v, &["currency", "currencyCode"]);
+            let period = first_str(v, &["period", "unit", "payPeriod", "interval"])
+                .and_then(|p| salary_period(&p));
+            (min.is_some() || max.is_some()).then_some(Salary { min, max, currency, period })
+        }
+        Value::String(text) => {
+            let mut amounts = text
+                .split(['-', '–'])
+                .filter_map(|part| parse_amount(part.split('/').next().unwrap_or(part)));
+            let min = amounts.next();
+            let max = amounts.next().or_else(|| min.clone());
+            min.as_ref()?;
+            Some(Salary { min, max, currency: currency_of_text(text), period: salary_period(text) })
+        }
+        _ => None,
+    }
+}
+
+/// "fulltime" / "Full-time" / "FullTime" -> "FULLTIME" (gaya JSearch).
+pub fn employment_type_code(s: &str) -> String {
+    s.chars().filter(|c| c.is_ascii_alphabetic()).collect::<String>().to_uppercase()
+}
+
+/// datePosted: RFC3339, "YYYY-MM-DD HH:MM:SS", "YYYY-MM-DD" atau epoch (detik / ms).
+fn posted_at(obj: &Value) -> Option<DateTime<Utc>> {
+    let v = ["datePosted", "postedAt", "listedAt", "postedDate"].iter().find_map(|k| obj.get(*k))?;
+    if let Some(n) = v.as_i64() {
+        let secs = if n > 10_000_000_000 { n / 1000 } else { n };
+        return DateTime::from_timestamp(secs, 0);
+    }
+    let s = v.as_str()?.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+        return Some(dt.and_utc());
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc())
+}
+
+/// Map item LinkedIn (hasil /search maupun /get) ke `Job` dengan prefix id `li_`.
+/// Semua field yang tersedia di payload jobs-api14 diisi; payload mentah disimpan di `raw_json`.
+/// Return `None` jika item tidak punya id.
+pub fn job_from_linkedin(obj: &Value) -> Option<Job> {
+    let id = first_str(obj, &["id"])?;
+    let job_id = format!("li_{id}");
+
+    let li_url = first_str(obj, &["linkedinUrl", "url"]);
+    let external_apply = first_str(obj, &["applyUrl", "applicationUrl", "externalApplyUrl", "companyApplyUrl"])
+        .filter(|u| !u.contains("linkedin.com"));
+    let employer_name = first_str(obj, &["companyName", "company.name"]);
+
+    let mut apply_options: Vec<ApplyOption> = Vec::new();
+    if let Some(link) = &li_url {
+        apply_options.push(ApplyOption {
+            job_id: job_id.clone(),
+            publisher: Some("LinkedIn".into()),
+            apply_link: Some(link.clone()),
+            is_direct: Some(false),
+            ..Default::default()
+        });
+    }
+    if let Some(link) = &external_apply {
+        apply_options.push(ApplyOption {
+            job_id: job_id.clone(),
+            publisher: employer_name.clone().or_else(|| Some("Company site".into())),
+            apply_link: Some(link.clone()),
+            is_direct: Some(true),
+            ..Default::default()
+        });
+    }
+
+    // workplaceType: remote / hybrid / onSite; workplaceTypes: ["remote", ...];
+    // workRemoteAllowed: true/false
+    let workplace = first_str(obj, &["workplaceType", "workplaceTypes", "workRemoteAllowed"]);
+    let job_is_remote = workplace
+        .as_deref()
+        .map(|w| w.eq_ignore_ascii_case("remote") || w.eq_ignore_ascii_case("true"))
+        .or_else(|| obj.get("remote").and_then(|v| v.as_bool()));
+
+    let employment_text = first_str(obj, &["employmentType", "employmentTypes", "jobType"]);
+    let posted = posted_at(obj);
+    let salary = salary_of(obj);
+    let salary_json = ["salary", "salaryRange", "compensation"].iter().find_map(|k| obj.get(*k)).cloned();
+
+    Some(Job {
+        job_id,
+        employer_name,
+        employer_logo: first_str(obj, &["companyLogo", "company.logo", "companyLogoUrl"]),
+        employer_website: first_str(obj, &["companyWebsite", "company.website"]),
+        employer_linkedin: first_str(obj, &["companyLinkedinUrl", "companyUrl", "company.linkedinUrl", "company.url"]),
+        job_publisher: Some("LinkedIn".into()),
+        job_employment_type: employment_text.as_deref().map(employment_type_code),
+        job_employment_type_text: employment_text,
+        job_title: first_str(obj, &["title"]),
+        job_apply_is_direct: Some(external_apply.is_some()),
+        job_apply_link: external_apply.or_else(|| li_url.clone()),
+        job_description: first_str(obj, &["description", "descriptionText"]),
+        job_is_remote,
+        job_posted_human_readable: first_str(obj, &["postedTimeAgo", "datePostedText", "listedAtText"]),
+        job_posted_at_timestamp: posted.map(|d| d.timestamp()),
+        job_posted_at_datetime_utc: posted,
+        job_location: first_str(obj, &["location", "formattedLocation"]),
+        job_benefits_json: obj.get("benefits").filter(|v| v.is_array()).cloned(),
+        job_salary_json: salary_json,
+        job_min_salary: salary.as_ref().and_then(|s| s.min.clone()),
+        job_max_salary: salary.as_ref().and_then(|s| s.max.clone()),
+        job_salary_currency: salary.as_ref().and_then(|s| s.currency.clone()),
+        job_salary_period: salary.as_ref().and_then(|s| s.period.clone()),
+        raw_json: Some(obj.clone()),
+        apply_options,
+        ..Default::default()
+    })
+}
+
+/// Ambil nextToken dari response search (bisa di `meta` atau top-level).
+fn next_token_of(search_json: &Value) -> Option<String> {
+    search_json
+        .get("meta").and_then(|m| m.get("nextToken")).and_then(|v| v.as_str())
+        .or_else(|| search_json.get("nextToken").and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+}
+
+#[async_trait::async_trait]
+impl JobSource for LinkedInApiClient {
+    fn name(&self) -> &'static str {
+        "linkedin"
+    }
+
+    fn host(&self) -> &str {
+        &self.host
+    }
+
+    fn enriches(&self) -> bool {
+        true
+    }
+
+    fn enrich_concurrency(&self) -> usize {
+        self.enrich_concurrency
+    }
+
+    async fn search(&self, req: &SearchRequest) -> anyhow::Result<SearchPage> {
+        let next_token = match &req.cursor {
+            Cursor::Token(t) => Some(t.as_str()),
+            _ => None,
+        };
+
+        let search_json = LinkedInApiClient::search(
+            self,
+            &req.query,
+            req.experience_levels.as_deref(),
+            req.workplace_types.as_deref(),
+            req.location.as_deref(),
+            req.date_posted.as_deref(),
+            req.employment_types.as_deref(),
+            next_token,
+        )
+        .await?;
+
+        // data: [ { id, title, companyName, location, datePosted, ... } ]
+        let jobs = search_json
+            .get("data")
+            .and_then(|d| d.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(job_from_linkedin)
+            .map(|mut job| {
+                job.search_query = Some(req.query.clone());
+                job
+            })
+            .collect();
+
+        Ok(SearchPage { jobs, next: next_token_of(&search_json).map(Cursor::Token) })
+    }
+
+    /// Enrichment via /v2/linkedin/get (deskripsi lengkap dsb).
+    async fn enrich(&self, job: Job) -> anyhow::Result<Job> {
+        let li_id = job.job_id.strip_prefix("li_").unwrap_or(&job.job_id);
+        let v = self.get_job(li_id).await?;
+
+        // API bisa return {"data": {...}} atau langsung {...}
+        let detail_obj = v.get("data").cloned().unwrap_or(v);
+        let mut detail = job_from_linkedin(&detail_obj)
+            .with_context(|| format!("LinkedIn get({li_id}) tanpa id"))?;
+        detail.search_query = job.search_query.or(detail.search_query);
+        Ok(detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Response /v2/linkedin/get (bentuk `{"data": {...}}`) yang disimpan di fixtures.
+    fn recorded_get() -> Value {
+        let v: Value = serde_json::from_str(include_str!("../fixtures/linkedin/get.json")).unwrap();
+        v.get("data").cloned().unwrap()
+    }
+
+    #[test]
+    fn maps_recorded_get_payload() {
+        let job = job_from_linkedin(&recorded_get()).unwrap();
+        assert_eq!(job.job_id, "li_4012345678");
+        assert_eq!(job.job_title.as_deref(), Some("Senior Rust Engineer"));
+        assert_eq!(job.employer_name.as_deref(), Some("Example Labs"));
+        assert_eq!(job.employer_logo.as_deref(), Some("https://media.licdn.com/dms/image/example-labs.png"));
+        assert_eq!(job.employer_linkedin.as_deref(), Some("https://www.linkedin.com/company/example-labs"));
+        assert_eq!(job.job_publisher.as_deref(), Some("LinkedIn"));
+        assert_eq!(job.job_employment_type.as_deref(), Some("FULLTIME"));
+        assert_eq!(job.job_employment_type_text.as_deref(), Some("Full-time"));
+        assert_eq!(job.job_location.as_deref(), Some("Berlin, Germany"));
+        assert_eq!(job.job_is_remote, Some(true));
+        assert_eq!(job.job_posted_at_datetime_utc.map(|d| d.to_rfc3339()), Some("2026-09-28T09:30:00+00:00".into()));
+        assert_eq!(job.job_posted_human_readable.as_deref(), Some("2 weeks ago"));
+        assert_eq!(job.job_min_salary, BigDecimal::from_str("90000").ok());
+        assert_eq!(job.job_max_salary, BigDecimal::from_str("120000").ok());
+        assert_eq!(job.job_salary_currency.as_deref(), Some("EUR"));
+        assert_eq!(job.job_salary_period.as_deref(), Some("YEAR"));
+        assert!(job.job_description.as_deref().unwrap_or("").contains("tokio"));
+        assert_eq!(job.job_benefits_json.as_ref().and_then(|b| b.as_array()).map(Vec::len), Some(2));
+
+        // apply: LinkedIn + situs perusahaan
+        assert_eq!(job.job_apply_link.as_deref(), Some("https://careers.example.com/jobs/rust"));
+        assert_eq!(job.job_apply_is_direct, Some(true));
+        let links: Vec<_> = job.apply_options.iter().filter_map(|o| o.apply_link.as_deref()).collect();
+        assert_eq!(links, ["https://www.linkedin.com/jobs/view/4012345678", "https://careers.example.com/jobs/rust"]);
+        assert!(job.raw_json.is_some());
+    }
+
+    #[test]
+    fn reads_workplace_types_array_and_remote_flag() {
+        let mut obj = recorded_get();
+        let map = obj.as_object_mut().unwrap();
+        map.remove("workplaceTypes");
+        map.insert("workRemoteAllowed".into(), Value::Bool(false));
+        assert_eq!(job_from_linkedin(&obj).unwrap().job_is_remote, Some(false));
+
+        let obj = serde_json::json!({ "id": 1, "workplaceTypes": ["hybrid", "remote"] });
+        assert_eq!(job_from_linkedin(&obj).unwrap().job_is_remote, Some(false));
+
+        let obj = serde_json::json!({ "id": 2, "workRemoteAllowed": true });
+        assert_eq!(job_from_linkedin(&obj).unwrap().job_is_remote, Some(true));
+
+        let obj = serde_json::json!({ "id": 3 });
+        assert_eq!(job_from_linkedin(&obj).unwrap().job_is_remote, None);
+    }
+
+    #[test]
+    fn parse_amount_table() {
+        for (input, expected) in [
+            ("120,000", Some("120000")),
+            ("120k", Some("120000")),
+            ("1.5k", Some("1500")),
+            ("45.50", Some("45.50")),
+            ("€50.000", Some("50000")),
+            ("Rp 15.000.000", Some("15000000")),
+            ("Rp 15.000.000,50", Some("15000000.50")),
+            ("$1,234.56", Some("1234.56")),
+            ("USD 90000", Some("90000")),
+            ("1.234.5", None),
+            ("1.234,567.000", None),
+            ("12345.000", None),
+            ("50.", None),
+            ("", None),
+            ("n/a", None),
+        ] {
+            let expected = expected.map(|e| BigDecimal::from_str(e).unwrap());
+            assert_eq!(parse_amount(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn first_str_reads_scalars_and_arrays() {
+        let obj = serde_json::json!({
+            "a": " x ", "n": 7, "b": true, "arr": ["", "first", "second"], "nested": { "k": "v" }, "empty": ""
+        });
+        assert_eq!(first_str(&obj, &["a"]).as_deref(), Some("x"));
+        assert_eq!(first_str(&obj, &["n"]).as_deref(), Some("7"));
+        assert_eq!(first_str(&obj, &["b"]).as_deref(), Some("true"));
+        assert_eq!(first_str(&obj, &["arr"]).as_deref(), Some("first"));
+        assert_eq!(first_str(&obj, &["missing", "empty", "nested.k"]).as_deref(), Some("v"));
+        assert_eq!(first_str(&obj, &["empty", "nested"]), None);
+    }
+}
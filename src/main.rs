@@ -64,38 +64,196 @@ mod api_client;
 mod openai_client;
 mod handlers;
 mod linkedin_client; // ⬅️ tambahkan
+mod job_source;
+mod retry;
+mod usage;
+mod scheduler;
+mod refresh;
+mod salary;
+mod feed_source;
+mod ats_source;
+mod importer;
+mod mock_providers;
+mod cassette;
+mod search_cache;
+mod dedup;
+mod expiry;
+mod link_checker;
+mod description;
+mod exchange_rates;
+mod skills;
+mod language;
+mod workplace;
 
 
 use std::env;
+use std::sync::Arc;
 use dotenv::dotenv;
 use warp::Filter; // ← tambahkan kembali
 
 
+/// Batas ukuran upload /import (50 MB).
+const IMPORT_MAX_BYTES: u64 = 50 * 1024 * 1024;
+
 #[tokio::main]
 async fn main() {
     dotenv().ok();
     
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let rapidapi_key = env::var("RAPIDAPI_KEY").expect("RAPIDAPI_KEY must be set");
-    let openai_key = env::var("OPENAI_API_KEY").unwrap_or_default();
+
+    // CLI: `cargo run -- import <file> [--format json|ndjson|csv] [--map field=Header,..]`
+    //      `cargo run -- dedup` (hitung ulang duplikat lintas sumber)
+    //      `cargo run -- salary-normalize` (hitung ulang gaji tahunan, mis. setelah kurs diubah)
+    //      `cargo run -- skills` (ekstrak ulang skill semua job, mis. setelah taksonomi diedit)
+    //      `cargo run -- language` (deteksi bahasa job lama yang job_posting_language-nya kosong)
+    //      `cargo run -- workplace` (klasifikasi ulang remote/hybrid/onsite + wilayah remote)
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(|a| a == "import").unwrap_or(false) {
+        let db = database::Database::new(&database_url).await.expect("Failed to connect to database");
+        std::process::exit(importer::run_cli(&db, &args[1..]).await);
+    }
+    if args.first().map(|a| a == "dedup").unwrap_or(false) {
+        let db = database::Database::new(&database_url).await.expect("Failed to connect to database");
+        std::process::exit(dedup::run_cli(&db).await);
+    }
+    if args.first().map(|a| a == "salary-normalize").unwrap_or(false) {
+        let db = database::Database::new(&database_url).await.expect("Failed to connect to database");
+        std::process::exit(salary::run_normalize_cli(&db).await);
+    }
+    if args.first().map(|a| a == "skills").unwrap_or(false) {
+        let db = database::Database::new(&database_url).await.expect("Failed to connect to database");
+        std::process::exit(skills::run_cli(&db).await);
+    }
+    if args.first().map(|a| a == "language").unwrap_or(false) {
+        let db = database::Database::new(&database_url).await.expect("Failed to connect to database");
+        std::process::exit(language::run_cli(&db).await);
+    }
+    if args.first().map(|a| a == "workplace").unwrap_or(false) {
+        let db = database::Database::new(&database_url).await.expect("Failed to connect to database");
+        std::process::exit(workplace::run_cli(&db).await);
+    }
+
+    // `--mock-providers` (atau MOCK_PROVIDERS=1): JSearch/LinkedIn/OpenAI dilayani dari fixture lokal
+    let mock = if mock_providers::enabled(&args) {
+        Some(mock_providers::spawn().expect("Failed to start mock providers"))
+    } else {
+        None
+    };
+
+    let rapidapi_key = match &mock {
+        Some(_) => env::var("RAPIDAPI_KEY").unwrap_or_else(|_| "mock".to_string()),
+        None => env::var("RAPIDAPI_KEY").expect("RAPIDAPI_KEY must be set"),
+    };
+    let mut openai_key = env::var("OPENAI_API_KEY").unwrap_or_default();
+    if mock.is_some() && openai_key.is_empty() {
+        openai_key = "mock".to_string();
+    }
+
+    // Base URL provider: default API asli; bisa dioverride lewat .env, mode mock menimpa keduanya
+    let jsearch_base_url = mock
+        .map(|m| m.jsearch_base_url())
+        .or_else(|| env::var("JSEARCH_BASE_URL").ok().filter(|s| !s.trim().is_empty()));
+    let linkedin_base_url = mock
+        .map(|m| m.linkedin_base_url())
+        .or_else(|| env::var("LINKEDIN_BASE_URL").ok().filter(|s| !s.trim().is_empty()));
+    let openai_base_url = mock
+        .map(|m| m.openai_base_url())
+        .or_else(|| env::var("OPENAI_BASE_URL").ok().filter(|s| !s.trim().is_empty()));
     
     let db = database::Database::new(&database_url).await.expect("Failed to connect to database");
-    let api_client = api_client::JobApiClient::new(rapidapi_key.clone(), "jsearch.p.rapidapi.com".to_string());
+    let usage_tracker = usage::UsageTracker::spawn(db.clone());
+    let retry_policy = retry::RetryPolicy::from_env();
+    // Rekam / replay response provider (HTTP_CASSETTE=record|replay|auto)
+    let http_cassette = cassette::Cassette::from_env();
+    if http_cassette.mode() != cassette::CassetteMode::Off {
+        println!("HTTP cassette {:?} di {}", http_cassette.mode(), http_cassette.dir().display());
+    }
+
+    let mut api_client = api_client::JobApiClient::new(rapidapi_key.clone(), "jsearch.p.rapidapi.com".to_string())
+        .with_retry(retry_policy.clone())
+        .with_usage(usage_tracker.clone())
+        .with_cassette(http_cassette.clone());
+    if let Some(url) = &jsearch_base_url {
+        api_client = api_client.with_base_url(url);
+    }
+
+
+    let mut openai_client = openai_client::OpenAIClient::new(openai_key).with_cassette(http_cassette.clone());
+    if let Some(url) = &openai_base_url {
+        openai_client = openai_client.with_base_url(url);
+    }
+    let enrich_concurrency: usize = env::var("LINKEDIN_ENRICH_CONCURRENCY")
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(4);
+    let mut li_client = linkedin_client::LinkedInApiClient::new(rapidapi_key.clone())
+        .with_retry(retry_policy.clone())
+        .with_enrich_concurrency(enrich_concurrency)
+        .with_usage(usage_tracker.clone())
+        .with_cassette(http_cassette.clone());
+    if let Some(url) = &linkedin_base_url {
+        li_client = li_client.with_base_url(url);
+    }
+
+    // Feed RSS/Atom (JOB_FEEDS) + poller background
+    let feed_source = feed_source::FeedSource::from_env();
+    feed_source::spawn_poller(db.clone(), feed_source.clone());
+
+    // Job board ATS publik (GREENHOUSE_*/LEVER_*/ASHBY_* di .env)
+    let ats_sources: Vec<ats_source::AtsSource> = ats_source::AtsKind::ALL
+        .iter()
+        .map(|kind| ats_source::AtsSource::from_env(*kind).with_retry(retry_policy.clone()))
+        .collect();
 
+    // Scheduler saved search (re-fetch berkala di background)
+    let mut sources: Vec<Arc<dyn job_source::JobSource>> = vec![
+        Arc::new(api_client.clone()),
+        Arc::new(li_client.clone()),
+        Arc::new(feed_source.clone()),
+    ];
+    for ats in &ats_sources {
+        sources.push(Arc::new(ats.clone()));
+    }
+    scheduler::spawn(db.clone(), sources, usage_tracker.clone());
 
-    let openai_client = openai_client::OpenAIClient::new(openai_key);
-    let li_client = linkedin_client::LinkedInApiClient::new(rapidapi_key.clone());
+    // Sweep expiry (lewat tanggal kedaluwarsa / tidak terlihat N hari)
+    expiry::spawn(db.clone());
+
+    // Cek liveness link apply di background
+    let link_checker = link_checker::LinkChecker::from_env();
+    link_checker::spawn(db.clone(), link_checker.clone());
 
 let fetch_li = warp::post()
     .and(warp::path("fetch_li"))
     .and(warp::body::form())
     .and(with_db(db.clone()))
     .and(with_li_client(li_client.clone()))
-    .and_then(|params, db, li_client| async move {
-        handlers::fetch_linkedin_handler(params, db, li_client).await
+    .and(with_usage(usage_tracker.clone()))
+    .and_then(|params, db, li_client, usage| async move {
+        handlers::fetch_linkedin_handler(params, db, li_client, usage).await
     });
 
 
+let fetch_feed = warp::post()
+    .and(warp::path("fetch_feed"))
+    .and(warp::body::form())
+    .and(with_db(db.clone()))
+    .and(with_feed_source(feed_source.clone()))
+    .and(with_usage(usage_tracker.clone()))
+    .and_then(|params, db, feeds, usage| async move {
+        handlers::fetch_feed_handler(params, db, feeds, usage).await
+    });
+
+let fetch_ats = warp::post()
+    .and(warp::path("fetch_ats"))
+    .and(warp::body::form())
+    .and(with_db(db.clone()))
+    .and(with_ats_sources(ats_sources.clone()))
+    .and(with_usage(usage_tracker.clone()))
+    .and_then(|params, db, boards, usage| async move {
+        handlers::fetch_ats_handler(params, db, boards, usage).await
+    });
+
     // Routes
     let index = warp::get()
         .and(warp::path::end())
@@ -106,8 +264,9 @@ let fetch_li = warp::post()
         .and(warp::body::form())
         .and(with_db(db.clone()))
         .and(with_api_client(api_client.clone()))
-        .and_then(|params, db, api_client| async move {
-            handlers::fetch_handler(params, db, api_client).await
+        .and(with_usage(usage_tracker.clone()))
+        .and_then(|params, db, api_client, usage| async move {
+            handlers::fetch_handler(params, db, api_client, usage).await
         });
     
    let list = warp::get()
@@ -115,9 +274,10 @@ let fetch_li = warp::post()
     .and(warp::query::<std::collections::HashMap<String, String>>())
     .and(with_db(db.clone()))
     .and_then(|query_map: std::collections::HashMap<String, String>, db: database::Database| async move {
-        let query = query_map.get("q").cloned();
+        let filter = database::ListFilter::from_query(&query_map);
         let page: usize = query_map.get("page").and_then(|s| s.parse().ok()).unwrap_or(1);
-        handlers::list_handler(query, page, db).await
+        let sort = database::ListSort::from_param(query_map.get("sort").map(|s| s.as_str()));
+        handlers::list_handler(filter, sort, page, db).await
     });
 
 
@@ -127,12 +287,59 @@ let fetch_li = warp::post()
     .and(warp::path("view"))
     .and(warp::path::param::<String>())
     .and(warp::path::end())
+    .and(warp::query::<std::collections::HashMap<String, String>>())
     .and(with_db(db.clone()))
     .and(with_li_client(li_client.clone())) // ⬅️ inject LinkedIn client
-    .and_then(|job_id, db, li_client| async move {
-        handlers::view_handler(job_id, db, li_client).await
+    .and_then(|job_id, query_map: std::collections::HashMap<String, String>, db, li_client| async move {
+        let notice = query_map.get("notice").cloned().filter(|s| !s.is_empty());
+        handlers::view_handler(job_id, notice, db, li_client).await
     });
+
+    let refresh_job = warp::post()
+        .and(warp::path("refresh"))
+        .and(warp::path::end())
+        .and(warp::body::form())
+        .and(with_db(db.clone()))
+        .and(with_api_client(api_client.clone()))
+        .and(with_usage(usage_tracker.clone()))
+        .and_then(|form: std::collections::HashMap<String, String>, db, api_client, usage| async move {
+            let job_id = form.get("job_id").cloned().unwrap_or_default();
+            handlers::refresh_handler(job_id, db, api_client, usage).await
+        });
+
+    let refresh_stale = warp::post()
+        .and(warp::path("refresh_stale"))
+        .and(warp::body::form())
+        .and(with_db(db.clone()))
+        .and(with_api_client(api_client.clone()))
+        .and(with_usage(usage_tracker.clone()))
+        .and_then(|form: std::collections::HashMap<String, String>, db, api_client, usage| async move {
+            let hours: i64 = form.get("hours").and_then(|s| s.parse().ok()).unwrap_or(72);
+            let limit: i64 = form.get("limit").and_then(|s| s.parse().ok()).unwrap_or(20);
+            handlers::refresh_stale_handler(hours, limit, db, api_client, usage).await
+        });
     
+    let estimate_salaries = warp::post()
+        .and(warp::path("estimate_salaries"))
+        .and(warp::body::form())
+        .and(with_db(db.clone()))
+        .and(with_api_client(api_client.clone()))
+        .and(with_usage(usage_tracker.clone()))
+        .and_then(|form: std::collections::HashMap<String, String>, db, api_client, usage| async move {
+            let limit: i64 = form.get("limit").and_then(|s| s.parse().ok()).unwrap_or(20);
+            handlers::estimate_salaries_handler(limit, db, api_client, usage).await
+        });
+
+    let check_links = warp::post()
+        .and(warp::path("check_links"))
+        .and(warp::body::form())
+        .and(with_db(db.clone()))
+        .and(with_link_checker(link_checker.clone()))
+        .and_then(|form: std::collections::HashMap<String, String>, db, checker| async move {
+            let limit: i64 = form.get("limit").and_then(|s| s.parse().ok()).unwrap_or(50);
+            handlers::check_links_handler(limit, db, checker).await
+        });
+
     let analyze = warp::post()
         .and(warp::path("analyze"))
         .and(warp::body::form())
@@ -171,6 +378,62 @@ let fetch_li = warp::post()
             handlers::cover_generate_handler(params, db, openai_client).await
         });
     
+    let usage_page = warp::get()
+        .and(warp::path("usage"))
+        .and(warp::path::end())
+        .and(warp::query::<std::collections::HashMap<String, String>>())
+        .and(with_db(db.clone()))
+        .and(with_usage(usage_tracker.clone()))
+        .and_then(|query_map: std::collections::HashMap<String, String>, db, usage| async move {
+            let days: i64 = query_map.get("days").and_then(|s| s.parse().ok()).unwrap_or(14);
+            handlers::usage_handler(days, db, usage).await
+        });
+
+    let saved = warp::get()
+        .and(warp::path("saved"))
+        .and(warp::path::end())
+        .and(with_db(db.clone()))
+        .and_then(|db| async move { handlers::saved_handler(db).await });
+
+    let runs = warp::get()
+        .and(warp::path("runs"))
+        .and(warp::path::end())
+        .and(warp::query::<std::collections::HashMap<String, String>>())
+        .and(with_db(db.clone()))
+        .and_then(|query_map: std::collections::HashMap<String, String>, db| async move {
+            let source = query_map.get("source").cloned();
+            let page: usize = query_map.get("page").and_then(|s| s.parse().ok()).unwrap_or(1);
+            handlers::runs_handler(source, page, db).await
+        });
+
+    let run_detail = warp::get()
+        .and(warp::path("runs"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path::end())
+        .and(with_db(db.clone()))
+        .and_then(|id: u64, db| async move { handlers::run_detail_handler(id, db).await });
+
+    let saved_delete = warp::post()
+        .and(warp::path("saved_delete"))
+        .and(warp::body::form())
+        .and(with_db(db.clone()))
+        .and_then(|form: std::collections::HashMap<String, String>, db| async move {
+            let id: u64 = form.get("id").and_then(|s| s.parse().ok()).unwrap_or(0);
+            handlers::saved_delete_handler(id, db).await
+        });
+
+    let import_page = warp::get()
+        .and(warp::path("import"))
+        .and(warp::path::end())
+        .and_then(|| async { handlers::import_page_handler().await });
+
+    let import_upload = warp::post()
+        .and(warp::path("import"))
+        .and(warp::path::end())
+        .and(warp::multipart::form().max_length(IMPORT_MAX_BYTES))
+        .and(with_db(db.clone()))
+        .and_then(|form, db| async move { handlers::import_upload_handler(form, db).await });
+
     let static_files = warp::get()
         .and(warp::fs::dir("static"));
     
@@ -178,12 +441,25 @@ let fetch_li = warp::post()
         .or(fetch)
         .or(list)
         .or(view)
+        .or(refresh_job)
+        .or(refresh_stale)
+        .or(estimate_salaries)
+        .or(check_links)
         .or(analyze)
         .or(resume)
         .or(resume_save)
         .or(cover_generate)
+        .or(usage_page)
+        .or(saved)
+        .or(saved_delete)
+        .or(runs)
+        .or(run_detail)
+        .or(import_page)
+        .or(import_upload)
         .or(static_files)
-        .or(fetch_li);   // ⬅️ baru
+        .or(fetch_li)   // ⬅️ baru
+        .or(fetch_feed)
+        .or(fetch_ats);
     
     println!("Server started at http://localhost:3030");
     warp::serve(routes)
@@ -207,4 +483,24 @@ fn with_openai_client(client: openai_client::OpenAIClient) -> impl Filter<Extrac
 fn with_li_client(client: linkedin_client::LinkedInApiClient)
     -> impl Filter<Extract = (linkedin_client::LinkedInApiClient,), Error = std::convert::Infallible> + Clone {
     warp::any().map(move || client.clone())
-}
\ No newline at end of file
+}
+
+fn with_usage(usage: usage::UsageTracker)
+    -> impl Filter<Extract = (usage::UsageTracker,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || usage.clone())
+}
+
+fn with_feed_source(source: feed_source::FeedSource)
+    -> impl Filter<Extract = (feed_source::FeedSource,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || source.clone())
+}
+
+fn with_ats_sources(sources: Vec<ats_source::AtsSource>)
+    -> impl Filter<Extract = (Vec<ats_source::AtsSource>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || sources.clone())
+}
+
+fn with_link_checker(checker: link_checker::LinkChecker)
+    -> impl Filter<Extract = (link_checker::LinkChecker,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || checker.clone())
+}
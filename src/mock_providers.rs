@@ -0,0 +1,240 @@
+// src/mock_providers.rs
+//
+// Mode `--mock-providers`: server HTTP lokal yang meniru JSearch, LinkedIn (jobs-api14)
+// dan OpenAI dengan response kalengan dari file fixture, supaya aplikasi bisa
+// didemokan dan diuji tanpa jaringan / API key. Client diarahkan ke server ini lewat
+// base URL (`with_base_url`). File dibaca per request, jadi fixture bisa diedit
+// tanpa restart.
+//
+// Struktur `MOCK_PROVIDERS_DIR` (default `fixtures/mock`):
+//   jsearch/search.json            -> GET  /jsearch/search
+//                                     GET  /jsearch/job-details?job_id=.. (dicari di search.json)
+//   jsearch/estimated-salary.json  -> GET  /jsearch/estimated-salary
+//   linkedin/search.json           -> GET  /linkedin/search
+//   linkedin/get.json              -> GET  /linkedin/get?id=.. (map id -> detail, fallback item search)
+//   openai/analyze.json            -> POST /openai/chat/completions
+//   openai/cover_letter.json       -> POST /openai/chat/completions (prompt cover letter)
+
+use std::collections::HashMap;
+use std::env;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use warp::reply::Response;
+use warp::{Filter, Rejection, Reply};
+
+#[derive(Debug, Clone, Copy)]
+pub struct MockProviders {
+    pub addr: SocketAddr,
+}
+
+impl MockProviders {
+    pub fn jsearch_base_url(&self) -> String {
+        format!("http://{}/jsearch", self.addr)
+    }
+
+    pub fn linkedin_base_url(&self) -> String {
+        format!("http://{}/linkedin", self.addr)
+    }
+
+    pub fn openai_base_url(&self) -> String {
+        format!("http://{}/openai", self.addr)
+    }
+}
+
+/// Aktif jika argumen `--mock-providers` ada atau `MOCK_PROVIDERS=1`.
+pub fn enabled(args: &[String]) -> bool {
+    args.iter().any(|a| a == "--mock-providers")
+        || env::var("MOCK_PROVIDERS").map(|v| v == "1" || v.eq_ignore_ascii_case("true")).unwrap_or(false)
+}
+
+/// Jalankan server mock di 127.0.0.1:`MOCK_PROVIDERS_PORT` (default 0 = port acak).
+pub fn spawn() -> Result<MockProviders, warp::Error> {
+    let dir = PathBuf::from(env::var("MOCK_PROVIDERS_DIR").unwrap_or_else(|_| "fixtures/mock".to_string()));
+    let port: u16 = env::var("MOCK_PROVIDERS_PORT")
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(0);
+
+    let mock = serve(dir.clone(), port)?;
+    println!("Mock providers (fixture {}) at http://{}", dir.display(), mock.addr);
+    Ok(mock)
+}
+
+/// Jalankan server mock dengan fixture dari `dir` di 127.0.0.1:`port` (0 = port acak).
+pub fn serve(dir: PathBuf, port: u16) -> Result<MockProviders, warp::Error> {
+    let (addr, server) = warp::serve(routes(dir)).try_bind_ephemeral(([127, 0, 0, 1], port))?;
+    tokio::spawn(server);
+    Ok(MockProviders { addr })
+}
+
+fn routes(dir: PathBuf) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    let with_dir = warp::any().map(move || dir.clone());
+    let query = warp::query::<HashMap<String, String>>();
+
+    let js_search = warp::get()
+        .and(warp::path!("jsearch" / "search"))
+        .and(with_dir.clone())
+        .and_then(|dir: PathBuf| async move { Ok::<_, Rejection>(file_reply(&dir, "jsearch/search.json")) });
+
+    let js_details = warp::get()
+        .and(warp::path!("jsearch" / "job-details"))
+        .and(query)
+        .and(with_dir.clone())
+        .and_then(|q: HashMap<String, String>, dir: PathBuf| async move {
+            Ok::<_, Rejection>(jsearch_details(&dir, q.get("job_id").map(|s| s.as_str()).unwrap_or("")))
+        });
+
+    let js_salary = warp::get()
+        .and(warp::path!("jsearch" / "estimated-salary"))
+        .and(with_dir.clone())
+        .and_then(|dir: PathBuf| async move {
+            Ok::<_, Rejection>(file_reply(&dir, "jsearch/estimated-salary.json"))
+        });
+
+    let li_search = warp::get()
+        .and(warp::path!("linkedin" / "search"))
+        .and(with_dir.clone())
+        .and_then(|dir: PathBuf| async move { Ok::<_, Rejection>(file_reply(&dir, "linkedin/search.json")) });
+
+    let li_get = warp::get()
+        .and(warp::path!("linkedin" / "get"))
+        .and(query)
+        .and(with_dir.clone())
+        .and_then(|q: HashMap<String, String>, dir: PathBuf| async move {
+            Ok::<_, Rejection>(linkedin_get(&dir, q.get("id").map(|s| s.as_str()).unwrap_or("")))
+        });
+
+    let openai = warp::post()
+        .and(warp::path!("openai" / "chat" / "completions"))
+        .and(warp::body::json())
+        .and(with_dir)
+        .and_then(|body: Value, dir: PathBuf| async move { Ok::<_, Rejection>(openai_chat(&dir, &body)) });
+
+    js_search
+        .or(js_details)
+        .unify()
+        .or(js_salary)
+        .unify()
+        .or(li_search)
+        .unify()
+        .or(li_get)
+        .unify()
+        .or(openai)
+        .unify()
+}
+
+fn read_json(dir: &std::path::Path, rel: &str) -> Result<Value, String> {
+    let path = dir.join(rel);
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+    serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn json_reply(value: &Value, status: StatusCode) -> Response {
+    warp::reply::with_status(warp::reply::json(value), status).into_response()
+}
+
+fn error_reply(msg: String, status: StatusCode) -> Response {
+    eprintln!("mock provider: {msg}");
+    json_reply(&json!({ "status": "ERROR", "message": msg }), status)
+}
+
+fn file_reply(dir: &std::path::Path, rel: &str) -> Response {
+    match read_json(dir, rel) {
+        Ok(v) => json_reply(&v, StatusCode::OK),
+        Err(e) => error_reply(e, StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// `/job-details`: job dengan `job_id` yang sama di search.json, 404 jika tidak ada.
+fn jsearch_details(dir: &std::path::Path, job_id: &str) -> Response {
+    let search = match read_json(dir, "jsearch/search.json") {
+        Ok(v) => v,
+        Err(e) => return error_reply(e, StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    let found = search
+        .get("data")
+        .and_then(|d| d.as_array())
+        .into_iter()
+        .flatten()
+        .find(|j| j.get("job_id").and_then(|v| v.as_str()) == Some(job_id));
+    match found {
+        Some(job) => json_reply(
+            &json!({ "status": "OK", "request_id": "mock-jsearch-job-details", "data": [job] }),
+            StatusCode::OK,
+        ),
+        None => error_reply(format!("job_id '{job_id}' tidak ada di fixture"), StatusCode::NOT_FOUND),
+    }
+}
+
+/// `/get`: detail dari get.json (map id -> objek), fallback item search.json.
+fn linkedin_get(dir: &std::path::Path, id: &str) -> Response {
+    let detail = read_json(dir, "linkedin/get.json")
+        .ok()
+        .and_then(|m| m.get(id).cloned())
+        .or_else(|| {
+            read_json(dir, "linkedin/search.json").ok().and_then(|s| {
+                s.get("data")?
+                    .as_array()?
+                    .iter()
+                    .find(|j| {
+                        let v = j.get("id");
+                        v.and_then(|v| v.as_str()) == Some(id)
+                            || v.and_then(|v| v.as_u64()).map(|n| n.to_string()).as_deref() == Some(id)
+                    })
+                    .cloned()
+            })
+        });
+    match detail {
+        Some(d) => json_reply(&json!({ "success": true, "data": d }), StatusCode::OK),
+        None => error_reply(format!("id '{id}' tidak ada di fixture"), StatusCode::NOT_FOUND),
+    }
+}
+
+/// Chat completion: prompt cover letter -> cover_letter.json, selain itu analyze.json.
+fn openai_chat(dir: &std::path::Path, body: &Value) -> Response {
+    let prompt = body.get("messages").map(|m| m.to_string().to_lowercase()).unwrap_or_default();
+    let file = if prompt.contains("cover letter") { "openai/cover_letter.json" } else { "openai/analyze.json" };
+    file_reply(dir, file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_client::JobApiClient;
+    use crate::linkedin_client::{job_from_linkedin, LinkedInApiClient};
+
+    fn start() -> MockProviders {
+        serve(PathBuf::from("fixtures/mock"), 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn jsearch_search_returns_fixture_jobs() {
+        let mock = start();
+        let client = JobApiClient::new("test-key".into(), "jsearch.p.rapidapi.com".into())
+            .with_base_url(&mock.jsearch_base_url());
+        let resp = client.search("rust developer", 1, 1, "all", "id", "en").await.unwrap();
+        let ids: Vec<_> = resp.data.iter().map(|j| j.job_id.as_str()).collect();
+        assert_eq!(ids, ["mockJS001==", "mockJS002==", "mockJS003=="]);
+
+        let details = client.job_details("mockJS002==").await.unwrap().unwrap();
+        assert_eq!(details.job_id, "mockJS002==");
+        assert!(client.job_details("missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn linkedin_search_returns_fixture_jobs() {
+        let mock = start();
+        let client = LinkedInApiClient::new("test-key".into()).with_base_url(&mock.linkedin_base_url());
+        let resp = client.search("rust", None, None, None, None, None, None).await.unwrap();
+        let jobs: Vec<_> = resp["data"].as_array().unwrap().iter().filter_map(job_from_linkedin).collect();
+        let titles: Vec<_> = jobs.iter().filter_map(|j| j.job_title.as_deref()).collect();
+        assert_eq!(titles, ["Rust Backend Engineer", "Software Engineer, Data Platform"]);
+        assert_eq!(jobs[0].job_id, "li_4000000001");
+
+        let detail = client.get_job("4000000001").await.unwrap();
+        assert_eq!(job_from_linkedin(&detail["data"]).unwrap().job_id, "li_4000000001");
+    }
+}
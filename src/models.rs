@@ -2,7 +2,7 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use sqlx::types::BigDecimal;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 // default untuk field yang tidak dikirim API
 fn default_string() -> String { String::new() }
@@ -10,7 +10,7 @@ fn default_zero_i8() -> i8 { 0 }
 fn default_now() -> DateTime<Utc> { Utc::now() }
 
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, FromRow, Serialize, Deserialize)]
 pub struct Job {
     // --- teks / varchar ---
     pub job_id: String,
@@ -64,7 +64,11 @@ pub struct Job {
     // --- teks panjang ---
     #[serde(default = "default_string")]
     pub matching_analysis: String,
+    /// Markdown hasil `description::normalize` (lihat job_description_raw untuk aslinya).
     pub job_description: Option<String>,
+    /// Deskripsi apa adanya dari sumber (HTML / teks); NULL untuk baris sebelum normalisasi ada.
+    #[serde(default)]
+    pub job_description_raw: Option<String>,
     pub cover_letter: Option<String>,
 
     // --- waktu ---
@@ -80,21 +84,223 @@ pub struct Job {
     pub updated_at: DateTime<Utc>,
     #[serde(default = "default_zero_i8")]
     pub isdelete: i8,
+    /// Diisi saat refresh mendapati posting sudah hilang di sumber (NULL = masih ada).
+    #[serde(default)]
+    pub upstream_missing_at: Option<DateTime<Utc>>,
+    /// Terakhir muncul di hasil pencarian / import / refresh.
+    #[serde(default)]
+    pub last_seen_at: Option<DateTime<Utc>>,
+    /// Diisi sweep expiry (NULL = masih aktif); alasan: "expiration" | "not_seen".
+    #[serde(default)]
+    pub expired_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub expired_reason: Option<String>,
+    /// Job kanonik jika posting ini duplikat lintas sumber (NULL = job ini kanonik).
+    #[serde(default)]
+    pub canonical_job_id: Option<String>,
+    /// Gaji posting per tahun dalam `salary_annual_currency` (`SALARY_BASE_CURRENCY`);
+    /// NULL jika periode / kurs tidak diketahui. Diisi `salary::store_annual`.
+    #[serde(default)]
+    pub salary_annual_min: Option<BigDecimal>,
+    #[serde(default)]
+    pub salary_annual_max: Option<BigDecimal>,
+    #[serde(default)]
+    pub salary_annual_currency: Option<String>,
+    /// "remote" | "hybrid" | "onsite" hasil `workplace::classify` (NULL = tidak diketahui).
+    #[serde(default)]
+    pub workplace_type: Option<String>,
+    /// Wilayah job remote: "Worldwide", "US", "Europe, UK", ... (NULL = tidak diketahui).
+    #[serde(default)]
+    pub remote_region_restriction: Option<String>,
+
+    // Opsi apply dari API (array `apply_options` JSearch). Bukan kolom tabel jobs,
+    // disimpan terpisah ke job_apply_options.
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub apply_options: Vec<ApplyOption>,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, FromRow, Serialize, Deserialize)]
 pub struct ApplyOption {
+    // id & job_id tidak dikirim API (diisi DB / pemanggil)
+    #[serde(default)]
     pub id: u64,
+    #[serde(default = "default_string")]
     pub job_id: String,
     pub publisher: Option<String>,
     pub apply_link: Option<String>,
     // ⬇⬇ ubah i8 -> bool untuk decode API
     pub is_direct: Option<bool>,
+    #[serde(default = "default_now")]
     pub created_at: DateTime<Utc>,
+
+    // hasil link checker (NULL = belum dicek)
+    #[serde(default)]
+    pub link_status: Option<u16>,
+    #[serde(default)]
+    pub link_final_url: Option<String>,
+    /// true = hidup, false = mati (404/410/host tak terjangkau), NULL = belum dicek / tidak pasti
+    #[serde(default)]
+    pub link_alive: Option<bool>,
+    #[serde(default)]
+    pub link_error: Option<String>,
+    #[serde(default)]
+    pub link_checked_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct Resume {
     pub id: i32,
     pub description: String,
-}
\ No newline at end of file
+}
+
+// Satu panggilan keluar ke provider (tabel api_calls)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiCall {
+    pub host: String,
+    pub endpoint: String,
+    pub status_code: Option<u16>,
+    pub latency_ms: i64,
+    pub requests_remaining: Option<i64>,
+    pub ratelimit_json: Option<serde_json::Value>,
+    pub error: Option<String>,
+}
+
+// Rekap /usage: jumlah call per hari per host
+#[derive(Debug, Clone, FromRow)]
+pub struct UsageDay {
+    pub day: NaiveDate,
+    pub host: String,
+    pub calls: i64,
+    pub errors: i64,
+}
+
+// Sisa kuota terakhir yang dilaporkan header x-ratelimit-* per host
+#[derive(Debug, Clone, FromRow)]
+pub struct HostQuota {
+    pub host: String,
+    pub requests_remaining: Option<i64>,
+    pub ratelimit_json: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Pencarian tersimpan yang dijalankan ulang oleh scheduler (tabel saved_searches)
+#[derive(Debug, Clone, FromRow)]
+pub struct SavedSearch {
+    pub id: u64,
+    pub source: String,
+    pub query: String,
+    pub country: Option<String>,
+    pub language: Option<String>,
+    pub date_posted: Option<String>,
+    pub num_pages: i32,
+    pub location: Option<String>,
+    pub experience_levels: Option<String>,
+    pub workplace_types: Option<String>,
+    pub employment_types: Option<String>,
+    pub interval_minutes: i32,
+    pub is_active: bool,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Job yang baru pertama kali terlihat pada sebuah run saved search (search_run_jobs.is_new)
+#[derive(Debug, Clone, FromRow)]
+pub struct RunNewJob {
+    pub run_id: u64,
+    pub job_id: String,
+    pub job_title: Option<String>,
+}
+
+// Satu fetch (manual / scheduler / poller) di tabel search_runs
+#[derive(Debug, Clone, FromRow)]
+pub struct SearchRun {
+    pub id: u64,
+    pub source: String,
+    /// manual | scheduler | poller
+    pub origin: String,
+    pub saved_search_id: Option<u64>,
+    pub query: String,
+    pub params_json: Option<serde_json::Value>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub pages: i32,
+    pub result_count: i32,
+    pub new_count: i32,
+    pub updated_count: i32,
+    /// halaman yang dilayani dari search_cache
+    pub cached_pages: i32,
+    pub error: Option<String>,
+}
+
+impl SearchRun {
+    pub fn duration_secs(&self) -> f64 {
+        (self.finished_at - self.started_at).num_milliseconds() as f64 / 1000.0
+    }
+}
+
+// Job hasil sebuah run (join search_run_jobs -> jobs)
+#[derive(Debug, Clone, FromRow)]
+pub struct SearchRunJob {
+    pub job_id: String,
+    pub is_new: bool,
+    pub job_title: Option<String>,
+    pub employer_name: Option<String>,
+    pub job_location: Option<String>,
+}
+
+// Cache estimasi gaji JSearch per (judul, lokasi) ternormalisasi (tabel salary_estimates).
+// Hasil kosong juga disimpan (semua angka NULL) supaya tidak di-call ulang.
+#[derive(Debug, Clone, FromRow)]
+pub struct SalaryEstimate {
+    pub title_key: String,
+    pub location_key: String,
+    pub job_title: String,
+    pub location: String,
+    pub min_salary: Option<BigDecimal>,
+    pub max_salary: Option<BigDecimal>,
+    pub median_salary: Option<BigDecimal>,
+    pub salary_currency: Option<String>,
+    pub salary_period: Option<String>,
+    pub publishers: Option<String>,
+    pub estimates_json: Option<serde_json::Value>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+// Baris /list: job + estimasi gaji (LEFT JOIN salary_estimates)
+#[derive(Debug, Clone, FromRow)]
+pub struct JobListItem {
+    #[sqlx(flatten)]
+    pub job: Job,
+    pub est_min_salary: Option<BigDecimal>,
+    pub est_max_salary: Option<BigDecimal>,
+    pub est_median_salary: Option<BigDecimal>,
+    pub est_salary_currency: Option<String>,
+    pub est_salary_period: Option<String>,
+    /// jumlah job duplikat (sumber lain) yang ditautkan ke job ini
+    pub duplicate_count: i64,
+    /// jumlah opsi apply + yang terakhir dicek mati
+    pub link_count: i64,
+    pub dead_link_count: i64,
+}
+
+impl JobListItem {
+    /// Jumlah sumber tempat posting ini terlihat (job ini + duplikatnya).
+    pub fn source_count(&self) -> i64 {
+        self.duplicate_count + 1
+    }
+
+    /// Semua opsi apply sudah dicek dan mati.
+    pub fn links_dead(&self) -> bool {
+        self.link_count > 0 && self.dead_link_count == self.link_count
+    }
+}
+
+// Duplikat lintas sumber dari sebuah job kanonik (jobs.canonical_job_id)
+#[derive(Debug, Clone, FromRow)]
+pub struct DuplicateJob {
+    pub job_id: String,
+    pub job_title: Option<String>,
+    pub job_publisher: Option<String>,
+    pub job_apply_link: Option<String>,
+}
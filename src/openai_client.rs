@@ -2,6 +2,8 @@
 use reqwest;
 use serde::{Deserialize, Serialize};
 
+use crate::cassette::Cassette;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct ChatMessage {
     role: String,
@@ -28,12 +30,31 @@ struct ChatCompletionResponse {
 pub struct OpenAIClient {
     client: reqwest::Client,
     api_key: String,
+    base_url: String,
+    cassette: Cassette,
 }
 
 impl OpenAIClient {
     pub fn new(api_key: String) -> Self {
         let client = reqwest::Client::new();
-        Self { client, api_key }
+        Self {
+            client,
+            api_key,
+            base_url: "https://api.openai.com/v1".to_string(),
+            cassette: Cassette::default(),
+        }
+    }
+
+    /// Rekam / putar ulang response lewat cassette (`HTTP_CASSETTE`).
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    /// Ganti base URL `.../v1` (`OPENAI_BASE_URL`: proxy / server kompatibel / mock).
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
     }
 
     pub fn available(&self) -> bool {
@@ -44,7 +65,7 @@ impl OpenAIClient {
         &self,
         resume: &str,
         job_desc: &str
-    ) -> Result<String, reqwest::Error> {
+    ) -> anyhow::Result<String> {
         let system = "You are an expert technical recruiter. Compare a candidate resume against a job description. Output a concise analysis";
         let user = format!(
             "RESUME:\n{}\n\nJOB DESCRIPTION:\n{}\n\nTASK:\n\
@@ -65,15 +86,13 @@ impl OpenAIClient {
             ],
         };
 
-        let resp = self.client
-            .post("https://api.openai.com/v1/chat/completions")
+        let req = self.client
+            .post(format!("{}/chat/completions", self.base_url))
             .bearer_auth(&self.api_key)
-            .json(&request)
-            .send()
-            .await?
-            .error_for_status()?;
+            .json(&request);
+        let exchange = self.cassette.send("openai", req, None).await?.error_for_status()?;
 
-        let api_response: ChatCompletionResponse = resp.json().await?;
+        let api_response: ChatCompletionResponse = serde_json::from_str(&exchange.body)?;
         let text = api_response
             .choices
             .first()
@@ -87,7 +106,7 @@ impl OpenAIClient {
         &self,
         resume: &str,
         job: &serde_json::Value
-    ) -> Result<String, reqwest::Error> {
+    ) -> anyhow::Result<String> {
         let job_title = job.get("job_title").and_then(|v| v.as_str()).unwrap_or("");
         let employer  = job.get("employer_name").and_then(|v| v.as_str()).unwrap_or("");
         let location  = job.get("job_location").and_then(|v| v.as_str()).unwrap_or("");
@@ -115,15 +134,13 @@ impl OpenAIClient {
             ],
         };
 
-        let resp = self.client
-            .post("https://api.openai.com/v1/chat/completions")
+        let req = self.client
+            .post(format!("{}/chat/completions", self.base_url))
             .bearer_auth(&self.api_key)
-            .json(&request)
-            .send()
-            .await?
-            .error_for_status()?;
+            .json(&request);
+        let exchange = self.cassette.send("openai", req, None).await?.error_for_status()?;
 
-        let api_response: ChatCompletionResponse = resp.json().await?;
+        let api_response: ChatCompletionResponse = serde_json::from_str(&exchange.body)?;
         let text = api_response
             .choices
             .first()
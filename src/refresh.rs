@@ -0,0 +1,88 @@
+// src/refresh.rs
+//
+// Refresh job JSearch yang sudah tersimpan lewat endpoint `/job-details`:
+// upsert ulang job + opsi apply-nya, atau tandai `upstream_missing_at` jika
+// posting sudah tidak ada di sumber. Dipakai tombol Refresh di /view/{id} dan
+// bulk refresh baris basi (berdasarkan `updated_at`).
+
+use chrono::{Duration, Utc};
+
+use crate::api_client::JobApiClient;
+use crate::database::Database;
+use crate::job_source::{self, FetchError, NON_JSEARCH_PREFIXES};
+use crate::usage::UsageTracker;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshOutcome {
+    Updated,
+    /// posting sudah hilang di JSearch (404 / data kosong)
+    Missing,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RefreshSummary {
+    pub checked: usize,
+    pub updated: usize,
+    pub missing: usize,
+    pub failed: usize,
+    /// berhenti lebih awal karena soft limit harian
+    pub stopped: Option<String>,
+}
+
+/// Ambil ulang satu job dari JSearch dan simpan hasilnya.
+pub async fn refresh_job(
+    api: &JobApiClient,
+    db: &Database,
+    job_id: &str,
+) -> Result<RefreshOutcome, FetchError> {
+    let fresh = api
+        .job_details(job_id)
+        .await
+        .map_err(|e| FetchError::Source(anyhow::anyhow!(e)))?;
+
+    let Some(mut fresh) = fresh else {
+        db.mark_job_missing(job_id).await.map_err(FetchError::Db)?;
+        return Ok(RefreshOutcome::Missing);
+    };
+
+    // job-details tidak tahu query asal; pertahankan yang lama
+    if fresh.search_query.is_none() {
+        if let Some(old) = db.find_job(job_id).await.map_err(FetchError::Db)? {
+            fresh.search_query = old.search_query;
+        }
+    }
+    job_source::store_job(db, &fresh).await.map_err(FetchError::Db)?;
+    Ok(RefreshOutcome::Updated)
+}
+
+/// Refresh maksimal `limit` job JSearch yang belum di-update selama `older_than_hours` jam.
+/// Berhenti jika soft limit harian host JSearch tercapai.
+pub async fn refresh_stale(
+    api: &JobApiClient,
+    db: &Database,
+    usage: &UsageTracker,
+    older_than_hours: i64,
+    limit: i64,
+) -> Result<RefreshSummary, sqlx::Error> {
+    let older_than = Utc::now() - Duration::hours(older_than_hours);
+    let ids = db.stale_job_ids(older_than, NON_JSEARCH_PREFIXES, limit).await?;
+
+    let mut summary = RefreshSummary::default();
+    for job_id in ids {
+        if let Err(msg) = usage.check_soft_limit(job_source::JobSource::host(api)).await {
+            summary.stopped = Some(msg);
+            break;
+        }
+
+        summary.checked += 1;
+        match refresh_job(api, db, &job_id).await {
+            Ok(RefreshOutcome::Updated) => summary.updated += 1,
+            Ok(RefreshOutcome::Missing) => summary.missing += 1,
+            Err(e) => {
+                eprintln!("refresh({job_id}) error: {e}");
+                summary.failed += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
@@ -0,0 +1,318 @@
+// src/retry.rs
+//
+// Retry + backoff bersama untuk client RapidAPI (JSearch & LinkedIn).
+// - 429 / 5xx / error jaringan -> retry dengan exponential backoff + jitter
+// - Hormati header `Retry-After` dan `x-ratelimit-*-remaining` / `-reset`
+// - Pacing: jeda minimal antar request + tunggu reset jika kuota habis
+
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+use crate::usage::{self, UsageTracker};
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total percobaan (termasuk yang pertama). Minimal 1.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    /// Batas atas satu kali tunggu. Jika server minta tunggu lebih lama, menyerah.
+    pub max_delay: Duration,
+    /// Jeda minimal antar request ke host yang sama.
+    pub min_interval: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            min_interval: Duration::ZERO,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Baca dari env (opsional):
+    /// RAPIDAPI_MAX_ATTEMPTS, RAPIDAPI_BACKOFF_BASE_MS, RAPIDAPI_BACKOFF_MAX_MS, RAPIDAPI_MIN_INTERVAL_MS
+    pub fn from_env() -> Self {
+        fn ms(key: &str) -> Option<Duration> {
+            env::var(key).ok()?.trim().parse().ok().map(Duration::from_millis)
+        }
+
+        let d = Self::default();
+        Self {
+            max_attempts: env::var("RAPIDAPI_MAX_ATTEMPTS")
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(d.max_attempts)
+                .max(1),
+            base_delay: ms("RAPIDAPI_BACKOFF_BASE_MS").unwrap_or(d.base_delay),
+            max_delay: ms("RAPIDAPI_BACKOFF_MAX_MS").unwrap_or(d.max_delay),
+            min_interval: ms("RAPIDAPI_MIN_INTERVAL_MS").unwrap_or(d.min_interval),
+        }
+    }
+
+    /// Exponential backoff dengan "equal jitter": acak di [d / 2, d], d = min(base*2^n, max_delay).
+    fn backoff(&self, attempt: u32) -> Duration {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+        self.backoff_with(attempt, f64::from(nanos % 1000) / 1000.0)
+    }
+
+    /// `backoff` dengan faktor acak `jitter` di [0, 1).
+    fn backoff_with(&self, attempt: u32, jitter: f64) -> Duration {
+        let exp = self.base_delay.saturating_mul(1u32 << attempt.min(16));
+        let capped = exp.min(self.max_delay);
+        let half = capped / 2;
+        half + half.mul_f64(jitter.clamp(0.0, 1.0))
+    }
+}
+
+/// Pengirim request dengan retry. Clone murah; state pacing dibagi antar clone.
+/// Jika ada `UsageTracker`, setiap percobaan dicatat ke api_calls atas nama host logis client.
+#[derive(Clone)]
+pub struct Retrier {
+    policy: RetryPolicy,
+    not_before: Arc<Mutex<Option<Instant>>>,
+    usage: Option<(UsageTracker, String)>,
+}
+
+impl Retrier {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self { policy, not_before: Arc::new(Mutex::new(None)), usage: None }
+    }
+
+    /// `host` = host RapidAPI logis (bukan host dari URL), supaya base URL override / mock
+    /// tetap dihitung ke soft limit host yang sama.
+    pub fn with_usage(mut self, usage: UsageTracker, host: &str) -> Self {
+        self.usage = Some((usage, host.to_string()));
+        self
+    }
+
+    /// Satu percobaan: build, kirim, catat (host, endpoint, status, latency, header rate-limit).
+    async fn send_once(&self, req: RequestBuilder) -> reqwest::Result<Response> {
+        let (client, request) = req.build_split();
+        let request = request?;
+        let url = request.url().clone();
+        let started = Instant::now();
+        let result = client.execute(request).await;
+
+        if let Some((usage, host)) = &self.usage {
+            let call = match &result {
+                Ok(resp) => usage::api_call_from(
+                    host,
+                    &url,
+                    Some(resp.status().as_u16()),
+                    Some(resp.headers()),
+                    started.elapsed(),
+                    None,
+                ),
+                Err(e) => usage::api_call_from(host, &url, None, None, started.elapsed(), Some(e.to_string())),
+            };
+            usage.record(call);
+        }
+        result
+    }
+
+    async fn wait_turn(&self) {
+        let wait = {
+            let mut guard = self.not_before.lock().unwrap();
+            let now = Instant::now();
+            let start = guard.map(|t| t.max(now)).unwrap_or(now);
+            *guard = Some(start + self.policy.min_interval);
+            start - now
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn hold_until(&self, delay: Duration) {
+        let until = Instant::now() + delay;
+        let mut guard = self.not_before.lock().unwrap();
+        *guard = Some(guard.map(|t| t.max(until)).unwrap_or(until));
+    }
+
+    /// Kirim request; retry untuk error jaringan, 429 dan 5xx.
+    /// Response terakhir (bisa non-2xx) dikembalikan apa adanya ke caller.
+    pub async fn send(&self, req: RequestBuilder) -> reqwest::Result<Response> {
+        let mut attempt = 0u32;
+        loop {
+            attempt += 1;
+            let last = attempt >= self.policy.max_attempts;
+
+            // GET tanpa body stream selalu bisa di-clone; kalau tidak, kirim sekali saja
+            let Some(this_req) = req.try_clone() else {
+                self.wait_turn().await;
+                return self.send_once(req).await;
+            };
+
+            self.wait_turn().await;
+            let resp = match self.send_once(this_req).await {
+                Ok(r) => r,
+                Err(e) if !last && (e.is_timeout() || e.is_connect()) => {
+                    let delay = self.policy.backoff(attempt - 1);
+                    eprintln!("request error ({e}), retry {attempt}/{} in {delay:?}", self.policy.max_attempts);
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let status = resp.status();
+            let quota_wait = exhausted_quota_reset(status, resp.headers(), self.policy.max_delay);
+            if let Some(w) = quota_wait {
+                // kuota habis: request berikutnya ditahan sampai reset (maks max_delay)
+                self.hold_until(w.min(self.policy.max_delay));
+            }
+
+            let retryable = status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+            if !retryable || last {
+                return Ok(resp);
+            }
+
+            let delay = retry_after(resp.headers())
+                .or(quota_wait)
+                .unwrap_or_else(|| self.policy.backoff(attempt - 1));
+            if delay > self.policy.max_delay {
+                eprintln!("HTTP {status}: server minta tunggu {delay:?}, melebihi batas; menyerah");
+                return Ok(resp);
+            }
+
+            eprintln!("HTTP {status}, retry {attempt}/{} in {delay:?}", self.policy.max_attempts);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// `Retry-After`: detik atau HTTP-date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let v = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = v.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(v).ok()?;
+    (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()
+}
+
+/// Jika ada `x-ratelimit-*-remaining: 0`, kembalikan lama tunggu dari `x-ratelimit-*-reset`
+/// (detik). Tanpa header reset, pakai 1 detik.
+/// Reset lebih lama dari `max_delay` (mis. kuota bulanan RapidAPI yang reset beberapa hari
+/// lagi) hanya dihormati untuk 429; response sukses tidak menahan request berikutnya.
+fn exhausted_quota_reset(status: StatusCode, headers: &HeaderMap, max_delay: Duration) -> Option<Duration> {
+    let limited = status == StatusCode::TOO_MANY_REQUESTS;
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = name.as_str();
+            let prefix = name.strip_prefix("x-ratelimit-")?.strip_suffix("-remaining")?;
+            let remaining: i64 = value.to_str().ok()?.trim().parse().ok()?;
+            if remaining > 0 {
+                return None;
+            }
+            let reset = headers
+                .get(format!("x-ratelimit-{prefix}-reset"))
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.trim().parse::<u64>().ok())
+                .unwrap_or(1);
+            let wait = Duration::from_secs(reset);
+            (limited || wait <= max_delay).then_some(wait)
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            min_interval: Duration::ZERO,
+        }
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn backoff_doubles_with_equal_jitter() {
+        let p = policy();
+        assert_eq!(p.backoff_with(0, 0.0), Duration::from_millis(250));
+        assert_eq!(p.backoff_with(0, 1.0), Duration::from_millis(500));
+        assert_eq!(p.backoff_with(2, 0.0), Duration::from_millis(1000));
+        assert_eq!(p.backoff_with(2, 1.0), Duration::from_millis(2000));
+        for attempt in 0..6 {
+            let d = p.backoff(attempt);
+            let full = p.base_delay * (1 << attempt);
+            assert!(d >= full / 2 && d <= full, "attempt {attempt}: {d:?}");
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped_at_max_delay() {
+        let p = policy();
+        assert_eq!(p.backoff_with(10, 1.0), Duration::from_secs(30));
+        assert_eq!(p.backoff_with(40, 0.0), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn retry_after_seconds_and_http_date() {
+        assert_eq!(retry_after(&headers(&[("retry-after", " 7 ")])), Some(Duration::from_secs(7)));
+        assert_eq!(retry_after(&headers(&[("retry-after", "soon")])), None);
+        assert_eq!(retry_after(&HeaderMap::new()), None);
+
+        let at = (chrono::Utc::now() + chrono::Duration::seconds(120)).to_rfc2822();
+        let wait = retry_after(&headers(&[("retry-after", &at)])).unwrap();
+        assert!(wait > Duration::from_secs(100) && wait <= Duration::from_secs(120), "{wait:?}");
+
+        let past = (chrono::Utc::now() - chrono::Duration::seconds(60)).to_rfc2822();
+        assert_eq!(retry_after(&headers(&[("retry-after", &past)])), None);
+    }
+
+    #[test]
+    fn quota_reset_only_when_remaining_is_zero() {
+        let max = Duration::from_secs(30);
+        let h = headers(&[("x-ratelimit-requests-remaining", "3"), ("x-ratelimit-requests-reset", "10")]);
+        assert_eq!(exhausted_quota_reset(StatusCode::OK, &h, max), None);
+
+        let h = headers(&[("x-ratelimit-requests-remaining", "0"), ("x-ratelimit-requests-reset", "10")]);
+        assert_eq!(exhausted_quota_reset(StatusCode::OK, &h, max), Some(Duration::from_secs(10)));
+
+        let h = headers(&[("x-ratelimit-requests-remaining", "0")]);
+        assert_eq!(exhausted_quota_reset(StatusCode::OK, &h, max), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn long_quota_reset_only_honoured_for_429() {
+        let max = Duration::from_secs(30);
+        // kuota bulanan: reset 3 hari lagi
+        let h = headers(&[("x-ratelimit-requests-remaining", "0"), ("x-ratelimit-requests-reset", "259200")]);
+        assert_eq!(exhausted_quota_reset(StatusCode::OK, &h, max), None);
+        assert_eq!(
+            exhausted_quota_reset(StatusCode::TOO_MANY_REQUESTS, &h, max),
+            Some(Duration::from_secs(259200))
+        );
+
+        // kuota per detik habis + kuota bulanan habis: yang pendek tetap dipakai
+        let h = headers(&[
+            ("x-ratelimit-requests-remaining", "0"),
+            ("x-ratelimit-requests-reset", "259200"),
+            ("x-ratelimit-rate-remaining", "0"),
+            ("x-ratelimit-rate-reset", "2"),
+        ]);
+        assert_eq!(exhausted_quota_reset(StatusCode::OK, &h, max), Some(Duration::from_secs(2)));
+    }
+}
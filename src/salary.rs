@@ -0,0 +1,407 @@
+// src/salary.rs
+//
+// Estimasi gaji JSearch (`/estimated-salary`) untuk job yang tidak mencantumkan gaji.
+// Hasil di-cache di tabel salary_estimates per (judul, lokasi) ternormalisasi —
+// termasuk hasil kosong — supaya judul yang sama tidak memakan kuota berulang kali.
+// Juga normalisasi gaji posting ke nilai tahunan dalam satu mata uang (lihat bawah).
+
+use std::env;
+use std::str::FromStr;
+
+use chrono::{Duration, Utc};
+use sqlx::types::BigDecimal;
+
+use crate::api_client::{EstimatedSalary, JobApiClient};
+use crate::database::Database;
+use crate::exchange_rates::{self, ExchangeRates};
+use crate::job_source::{FetchError, JobSource};
+use crate::models::{Job, SalaryEstimate};
+use crate::usage::UsageTracker;
+
+/// Kunci cache: trim + lowercase, maks 255 karakter (sama dengan `SALARY_*_KEY_SQL` di database.rs).
+pub fn normalize_key(s: &str) -> String {
+    s.trim().to_lowercase().chars().take(255).collect()
+}
+
+/// Lokasi yang dipakai untuk estimasi: `job_location`, fallback `job_country`.
+pub fn estimate_location(job: &Job) -> String {
+    job.job_location
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .or(job.job_country.as_deref())
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Job tanpa gaji posting (min & max kosong) yang punya judul.
+pub fn needs_estimate(job: &Job) -> bool {
+    job.job_min_salary.is_none()
+        && job.job_max_salary.is_none()
+        && job.job_title.as_deref().map(|t| !t.trim().is_empty()).unwrap_or(false)
+}
+
+/// Umur cache (`SALARY_CACHE_DAYS`, default 30 hari).
+fn cache_ttl() -> Duration {
+    let days = env::var("SALARY_CACHE_DAYS")
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .filter(|d| *d > 0)
+        .unwrap_or(30);
+    Duration::days(days)
+}
+
+fn has_amount(est: &SalaryEstimate) -> bool {
+    est.min_salary.is_some() || est.max_salary.is_some() || est.median_salary.is_some()
+}
+
+fn decimal(v: f64) -> Option<BigDecimal> {
+    BigDecimal::from_str(&format!("{v:.2}")).ok()
+}
+
+/// Gabungkan baris per publisher: min terendah, max tertinggi, rata-rata median.
+/// Hanya baris dengan periode yang sama dengan baris pertama yang dihitung.
+fn aggregate(title: &str, location: &str, rows: &[EstimatedSalary]) -> SalaryEstimate {
+    let period = rows.iter().find_map(|r| r.salary_period.clone());
+    let same: Vec<&EstimatedSalary> = rows.iter().filter(|r| r.salary_period == period).collect();
+
+    let min = same.iter().filter_map(|r| r.min_salary).fold(None, |acc: Option<f64>, v| {
+        Some(acc.map_or(v, |a| a.min(v)))
+    });
+    let max = same.iter().filter_map(|r| r.max_salary).fold(None, |acc: Option<f64>, v| {
+        Some(acc.map_or(v, |a| a.max(v)))
+    });
+    let medians: Vec<f64> = same.iter().filter_map(|r| r.median_salary).collect();
+    let median = (!medians.is_empty()).then(|| medians.iter().sum::<f64>() / medians.len() as f64);
+
+    let publishers: Vec<&str> = same.iter().filter_map(|r| r.publisher_name.as_deref()).collect();
+
+    SalaryEstimate {
+        title_key: normalize_key(title),
+        location_key: normalize_key(location),
+        job_title: title.trim().to_string(),
+        location: location.trim().chars().take(255).collect(),
+        min_salary: min.and_then(decimal),
+        max_salary: max.and_then(decimal),
+        median_salary: median.and_then(decimal),
+        salary_currency: same.iter().find_map(|r| r.salary_currency.clone()),
+        salary_period: period,
+        publishers: (!publishers.is_empty()).then(|| publishers.join(", ")),
+        estimates_json: serde_json::to_value(rows).ok(),
+        fetched_at: Utc::now(),
+    }
+}
+
+/// Estimasi untuk (judul, lokasi): pakai cache jika masih segar, jika tidak call JSearch.
+/// Saat soft limit tercapai, cache lama (jika ada) tetap dipakai.
+/// `Ok(None)` = tidak ada angka estimasi.
+pub async fn estimate(
+    api: &JobApiClient,
+    db: &Database,
+    usage: &UsageTracker,
+    title: &str,
+    location: &str,
+) -> Result<Option<SalaryEstimate>, FetchError> {
+    let (title_key, location_key) = (normalize_key(title), normalize_key(location));
+    let cached = db
+        .get_salary_estimate(&title_key, &location_key)
+        .await
+        .map_err(FetchError::Db)?;
+
+    if let Some(c) = &cached {
+        if Utc::now() - c.fetched_at < cache_ttl() {
+            return Ok(cached.filter(has_amount));
+        }
+    }
+    if usage.check_soft_limit(api.host()).await.is_err() {
+        return Ok(cached.filter(has_amount));
+    }
+
+    let rows = api
+        .estimated_salary(title.trim(), location.trim())
+        .await
+        .map_err(|e| FetchError::Source(anyhow::anyhow!(e)))?;
+    let est = aggregate(title, location, &rows);
+    db.upsert_salary_estimate(&est).await.map_err(FetchError::Db)?;
+
+    Ok(Some(est).filter(has_amount))
+}
+
+/// Estimasi tersimpan untuk satu job (hanya jika gaji posting kosong), tanpa call API,
+/// berapa pun umurnya. Halaman detail hanya membaca cache; pengisian lewat
+/// `estimate_missing` (`POST /estimate_salaries`).
+pub async fn cached_estimate_for_job(db: &Database, job: &Job) -> Result<Option<SalaryEstimate>, sqlx::Error> {
+    if !needs_estimate(job) {
+        return Ok(None);
+    }
+    let title = job.job_title.as_deref().unwrap_or_default();
+    let cached = db
+        .get_salary_estimate(&normalize_key(title), &normalize_key(&estimate_location(job)))
+        .await?;
+    Ok(cached.filter(has_amount))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EstimateSummary {
+    pub checked: usize,
+    pub found: usize,
+    pub failed: usize,
+    /// berhenti lebih awal karena soft limit harian
+    pub stopped: Option<String>,
+}
+
+/// Isi cache untuk maksimal `limit` pasangan (judul, lokasi) job tanpa gaji.
+pub async fn estimate_missing(
+    api: &JobApiClient,
+    db: &Database,
+    usage: &UsageTracker,
+    limit: i64,
+) -> Result<EstimateSummary, sqlx::Error> {
+    let keys = db.salary_keys_without_estimate(limit).await?;
+
+    let mut summary = EstimateSummary::default();
+    for (title, location) in keys {
+        if let Err(msg) = usage.check_soft_limit(api.host()).await {
+            summary.stopped = Some(msg);
+            break;
+        }
+
+        summary.checked += 1;
+        match estimate(api, db, usage, &title, &location).await {
+            Ok(Some(_)) => summary.found += 1,
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("estimated_salary({title} @ {location}) error: {e}");
+                summary.failed += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Teks gaji ringkas, mis. "USD 90000 - 120000 / YEAR".
+pub fn salary_label(
+    min: Option<&BigDecimal>,
+    max: Option<&BigDecimal>,
+    currency: Option<&str>,
+    period: Option<&str>,
+) -> Option<String> {
+    let fmt = |d: &BigDecimal| d.with_scale(0).to_string();
+    let amount = match (min, max) {
+        (Some(a), Some(b)) if a == b => fmt(a),
+        (Some(a), Some(b)) => format!("{} - {}", fmt(a), fmt(b)),
+        (Some(a), None) => format!("≥ {}", fmt(a)),
+        (None, Some(b)) => format!("≤ {}", fmt(b)),
+        (None, None) => return None,
+    };
+    let mut label = match currency {
+        Some(c) if !c.is_empty() => format!("{c} {amount}"),
+        _ => amount,
+    };
+    if let Some(p) = period.filter(|p| !p.is_empty()) {
+        label.push_str(&format!(" / {p}"));
+    }
+    Some(label)
+}
+
+/// Label estimasi: rentang min-max, fallback median.
+pub fn estimate_label(est: &SalaryEstimate) -> Option<String> {
+    match (&est.min_salary, &est.max_salary) {
+        (None, None) => salary_label(
+            est.median_salary.as_ref(),
+            est.median_salary.as_ref(),
+            est.salary_currency.as_deref(),
+            est.salary_period.as_deref(),
+        ),
+        (min, max) => salary_label(
+            min.as_ref(),
+            max.as_ref(),
+            est.salary_currency.as_deref(),
+            est.salary_period.as_deref(),
+        ),
+    }
+}
+
+// ==== Normalisasi gaji tahunan ====
+// `job_min_salary` / `job_max_salary` bisa per jam, hari, minggu, bulan atau tahun
+// (`job_salary_period`) dalam mata uang apa pun (`job_salary_currency`). Untuk filter dan
+// urutan /list dihitung versi tahunan dalam `SALARY_BASE_CURRENCY` (kolom `salary_annual_*`).
+
+/// Pengali ke setahun: 40 jam x 52 minggu, 5 hari x 52 minggu.
+fn period_factor(period: &str) -> Option<f64> {
+    match period.trim().to_uppercase().as_str() {
+        "HOUR" | "HOURLY" | "HR" => Some(2080.0),
+        "DAY" | "DAILY" => Some(260.0),
+        "WEEK" | "WEEKLY" | "WK" => Some(52.0),
+        "MONTH" | "MONTHLY" | "MO" => Some(12.0),
+        "YEAR" | "YEARLY" | "ANNUAL" | "YR" => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Gaji posting dalam setahun + mata uang tujuan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnualSalary {
+    pub min: Option<BigDecimal>,
+    pub max: Option<BigDecimal>,
+    pub currency: String,
+}
+
+/// `None` jika gaji, periode atau kurs mata uangnya tidak diketahui.
+pub fn annualize(job: &Job, rates: &ExchangeRates) -> Option<AnnualSalary> {
+    let factor = period_factor(job.job_salary_period.as_deref()?)?;
+    let currency = job.job_salary_currency.as_deref().filter(|c| !c.trim().is_empty())?;
+    let to_annual = |d: &Option<BigDecimal>| -> Option<Option<f64>> {
+        match d.as_ref().and_then(|d| d.to_string().parse::<f64>().ok()) {
+            Some(v) if v > 0.0 => rates.convert(v * factor, currency).map(Some),
+            _ => Some(None),
+        }
+    };
+    let (mut min, mut max) = (to_annual(&job.job_min_salary)?, to_annual(&job.job_max_salary)?);
+    if let (Some(a), Some(b)) = (min, max) {
+        if a > b {
+            (min, max) = (Some(b), Some(a));
+        }
+    }
+    if min.is_none() && max.is_none() {
+        return None;
+    }
+    Some(AnnualSalary {
+        min: min.and_then(decimal),
+        max: max.and_then(decimal),
+        currency: rates.target.clone(),
+    })
+}
+
+/// Hitung + simpan gaji tahunan satu job (NULL jika tidak bisa dinormalisasi).
+pub async fn store_annual(db: &Database, job: &Job) -> Result<(), sqlx::Error> {
+    db.set_job_salary_annual(&job.job_id, annualize(job, exchange_rates::global()).as_ref())
+        .await
+}
+
+/// Label gaji tahunan untuk tampilan, mis. "≈ USD 93600 / tahun"; `None` jika gaji posting
+/// sudah tahunan dalam mata uang yang sama (tidak perlu ditampilkan dua kali).
+pub fn annual_label(job: &Job) -> Option<String> {
+    let currency = job.salary_annual_currency.as_deref()?;
+    let same_period = job.job_salary_period.as_deref().and_then(period_factor) == Some(1.0);
+    let same_currency = job
+        .job_salary_currency
+        .as_deref()
+        .map(|c| c.trim().eq_ignore_ascii_case(currency))
+        .unwrap_or(false);
+    if same_period && same_currency {
+        return None;
+    }
+    salary_label(job.salary_annual_min.as_ref(), job.salary_annual_max.as_ref(), Some(currency), None)
+        .map(|l| format!("≈ {l} / tahun"))
+}
+
+/// `job_finder salary-normalize`: hitung ulang gaji tahunan semua job, mis. setelah file kurs
+/// atau `SALARY_BASE_CURRENCY` diubah. Mengembalikan exit code.
+pub async fn run_normalize_cli(db: &Database) -> i32 {
+    let rates = exchange_rates::global();
+    let jobs = match db.jobs_with_posted_salary().await {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            eprintln!("salary-normalize error: {e}");
+            return 1;
+        }
+    };
+
+    let (mut normalized, mut skipped) = (0, 0);
+    for job in &jobs {
+        let annual = annualize(job, rates);
+        if annual.is_some() {
+            normalized += 1;
+        } else {
+            skipped += 1;
+        }
+        if let Err(e) = db.set_job_salary_annual(&job.job_id, annual.as_ref()).await {
+            eprintln!("salary-normalize error: {e}");
+            return 1;
+        }
+    }
+    println!(
+        "salary-normalize: {} job bergaji, {normalized} dinormalisasi ke {} / tahun, {skipped} dilewati (periode/kurs tidak diketahui)",
+        jobs.len(),
+        rates.target
+    );
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rates() -> ExchangeRates {
+        ExchangeRates::from_json(r#"{ "base": "USD", "rates": { "IDR": 16000, "EUR": 0.5 } }"#, "USD").unwrap()
+    }
+
+    fn job(min: Option<&str>, max: Option<&str>, currency: Option<&str>, period: Option<&str>) -> Job {
+        Job {
+            job_min_salary: min.map(|v| BigDecimal::from_str(v).unwrap()),
+            job_max_salary: max.map(|v| BigDecimal::from_str(v).unwrap()),
+            job_salary_currency: currency.map(str::to_string),
+            job_salary_period: period.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn annual(min: Option<&str>, max: Option<&str>) -> Option<AnnualSalary> {
+        Some(AnnualSalary {
+            min: min.map(|v| BigDecimal::from_str(v).unwrap()),
+            max: max.map(|v| BigDecimal::from_str(v).unwrap()),
+            currency: "USD".into(),
+        })
+    }
+
+    #[test]
+    fn period_factor_table() {
+        for (period, factor) in [
+            ("HOUR", Some(2080.0)),
+            ("hourly", Some(2080.0)),
+            ("DAY", Some(260.0)),
+            (" week ", Some(52.0)),
+            ("MONTH", Some(12.0)),
+            ("yr", Some(1.0)),
+            ("ANNUAL", Some(1.0)),
+            ("quarter", None),
+            ("", None),
+        ] {
+            assert_eq!(period_factor(period), factor, "{period}");
+        }
+    }
+
+    #[test]
+    fn annualize_table() {
+        let rates = rates();
+        for (input, expected) in [
+            (job(Some("50"), Some("60"), Some("USD"), Some("HOUR")), annual(Some("104000.00"), Some("124800.00"))),
+            (job(Some("16000000"), None, Some("IDR"), Some("MONTH")), annual(Some("12000.00"), None)),
+            (job(None, Some("4000"), Some("eur"), Some("month")), annual(None, Some("96000.00"))),
+            // min > max ditukar
+            (job(Some("90000"), Some("70000"), Some("USD"), Some("YEAR")), annual(Some("70000.00"), Some("90000.00"))),
+            // nilai <= 0 diabaikan
+            (job(Some("0"), Some("80000"), Some("USD"), Some("YEAR")), annual(None, Some("80000.00"))),
+            (job(Some("-5"), Some("0"), Some("USD"), Some("YEAR")), None),
+            (job(None, None, Some("USD"), Some("YEAR")), None),
+            // periode / mata uang tidak diketahui atau kosong
+            (job(Some("100"), None, Some("USD"), Some("QUARTER")), None),
+            (job(Some("100"), None, Some("JPY"), Some("YEAR")), None),
+            (job(Some("100"), None, Some(" "), Some("YEAR")), None),
+            (job(Some("100"), None, None, Some("YEAR")), None),
+            (job(Some("100"), None, Some("USD"), None), None),
+        ] {
+            assert_eq!(annualize(&input, &rates), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn annualize_with_empty_table_only_converts_target() {
+        let rates = ExchangeRates::empty("USD");
+        let usd = job(Some("1000"), None, Some("USD"), Some("WEEK"));
+        assert_eq!(annualize(&usd, &rates), annual(Some("52000.00"), None));
+        let idr = job(Some("1000"), None, Some("IDR"), Some("WEEK"));
+        assert_eq!(annualize(&idr, &rates), None);
+    }
+}
@@ -0,0 +1,87 @@
+// src/scheduler.rs
+//
+// Scheduler background: setiap tick, jalankan ulang saved search yang sudah jatuh
+// tempo (interval per baris), simpan hasil lewat `job_source::fetch_and_store`,
+// lalu catat run-nya di search_runs (origin "scheduler", saved_search_id terisi) beserta
+// job yang baru terlihat di search_run_jobs; /saved membaca riwayat dari sana.
+
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::database::Database;
+use crate::job_source::{self, JobSource, RunOrigin, SearchRequest};
+use crate::models::SavedSearch;
+use crate::usage::UsageTracker;
+
+/// Jalankan scheduler di task Tokio terpisah.
+/// `SCHEDULER_TICK_SECS` (default 60) mengatur seberapa sering cek jatuh tempo; 0 = nonaktif.
+pub fn spawn(db: Database, sources: Vec<Arc<dyn JobSource>>, usage: UsageTracker) {
+    let tick_secs: u64 = env::var("SCHEDULER_TICK_SECS")
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(60);
+    if tick_secs == 0 {
+        println!("Scheduler disabled (SCHEDULER_TICK_SECS=0)");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(tick_secs));
+        loop {
+            ticker.tick().await;
+
+            let due = match db.due_saved_searches().await {
+                Ok(d) => d,
+                Err(e) => {
+                    eprintln!("scheduler: due_saved_searches error: {e}");
+                    continue;
+                }
+            };
+
+            for saved in &due {
+                run_saved_search(&db, &sources, &usage, saved).await;
+            }
+        }
+    });
+}
+
+async fn run_saved_search(
+    db: &Database,
+    sources: &[Arc<dyn JobSource>],
+    usage: &UsageTracker,
+    saved: &SavedSearch,
+) {
+    let req = SearchRequest::from(saved);
+    let started_at = chrono::Utc::now();
+    let source = sources.iter().find(|s| s.name() == saved.source);
+    let outcome = match source {
+        None => Err(format!("unknown source '{}'", saved.source)),
+        Some(source) => match usage.check_soft_limit(source.host()).await {
+            Err(msg) => Err(msg),
+            Ok(()) => job_source::fetch_and_store(source.as_ref(), &req, db)
+                .await
+                .map_err(|e| e.to_string()),
+        },
+    };
+
+    match &outcome {
+        Ok(summary) => println!(
+            "scheduler: saved search #{} ({} '{}'): {} results, {} new",
+            saved.id, saved.source, saved.query, summary.saved, summary.new_job_ids.len()
+        ),
+        Err(msg) => eprintln!("scheduler: saved search #{} error: {msg}", saved.id),
+    }
+
+    // juga menggeser saved_searches.last_run_at
+    job_source::record_run(
+        db,
+        &saved.source,
+        RunOrigin::Scheduler(saved.id),
+        &req,
+        started_at,
+        outcome.as_ref().map_err(|e| e.as_str()),
+        0,
+    )
+    .await;
+}
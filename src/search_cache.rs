@@ -0,0 +1,198 @@
+// src/search_cache.rs
+//
+// Cache response search provider di tabel search_cache, supaya submit ulang form
+// /fetch dengan parameter yang sama dalam rentang TTL tidak memakan kuota RapidAPI.
+// Kunci = sha256(sumber, parameter pencarian, cursor/halaman); yang disimpan adalah
+// `SearchPage` hasil mapping (job + cursor berikutnya), bukan body mentah.
+//
+// `SEARCH_CACHE_TTL_MINUTES` (default 60; 0 = cache nonaktif).
+
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::database::Database;
+use crate::job_source::{Cursor, JobSource, SearchPage, SearchRequest};
+use crate::models::Job;
+use crate::usage::{SoftLimitExceeded, UsageTracker};
+
+/// TTL cache dari env; `None` = cache nonaktif.
+pub fn ttl_from_env() -> Option<Duration> {
+    let minutes = env::var("SEARCH_CACHE_TTL_MINUTES")
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .unwrap_or(60);
+    (minutes > 0).then(|| Duration::minutes(minutes))
+}
+
+/// 64 hex sha256 dari nama sumber + `query_key` (semua filter + num_pages) + cursor.
+pub fn cache_key(source: &str, req: &SearchRequest) -> String {
+    let cursor = req.cursor.encode().unwrap_or_default();
+    let digest = Sha256::digest(format!("{source}\n{}\n{cursor}", req.query_key()).as_bytes());
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Bungkus `JobSource` lain: `search` dilayani dari cache jika ada entry yang belum
+/// kedaluwarsa; hasil search asli disimpan ke cache. Enrichment tetap diteruskan ke
+/// sumber aslinya (job yang sudah punya deskripsi di DB tidak di-enrich ulang).
+pub struct CachedSource<'a> {
+    inner: &'a dyn JobSource,
+    db: &'a Database,
+    ttl: Option<Duration>,
+    /// lewati lookup (tetap tulis hasil baru ke cache)
+    force_refresh: bool,
+    usage: Option<&'a UsageTracker>,
+    hits: AtomicUsize,
+}
+
+impl<'a> CachedSource<'a> {
+    pub fn new(inner: &'a dyn JobSource, db: &'a Database, ttl: Option<Duration>, force_refresh: bool) -> Self {
+        Self { inner, db, ttl, force_refresh, usage: None, hits: AtomicUsize::new(0) }
+    }
+
+    /// Cek soft limit harian sebelum setiap search yang tidak terlayani cache
+    /// (error `SoftLimitExceeded`); cache hit tidak memakan kuota sehingga tidak dicek.
+    pub fn with_usage(mut self, usage: &'a UsageTracker) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Jumlah halaman yang dilayani dari cache.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    async fn lookup(&self, key: &str) -> Option<SearchPage> {
+        let cached = match self.db.get_search_cache(key).await {
+            Ok(c) => c?,
+            Err(e) => {
+                eprintln!("get_search_cache error: {e}");
+                return None;
+            }
+        };
+        match page_from_json(&cached) {
+            Ok(page) => page,
+            Err(e) => {
+                eprintln!("search_cache {key} tidak bisa dibaca (diabaikan): {e}");
+                None
+            }
+        }
+    }
+
+    /// Search ke sumber asli, didahului cek soft limit jika ada `usage`.
+    async fn search_inner(&self, req: &SearchRequest) -> anyhow::Result<SearchPage> {
+        if let Some(usage) = self.usage {
+            usage.check_soft_limit(self.inner.host()).await.map_err(SoftLimitExceeded)?;
+        }
+        self.inner.search(req).await
+    }
+}
+
+/// Bentuk yang disimpan di search_cache.response: `{"jobs": [...], "next": "p:2" | null}`.
+pub fn page_to_json(page: &SearchPage) -> serde_json::Value {
+    json!({
+        "jobs": page.jobs,
+        "next": page.next.as_ref().and_then(Cursor::encode),
+    })
+}
+
+/// Kebalikan `page_to_json`; `Ok(None)` jika field `jobs` tidak ada.
+pub fn page_from_json(cached: &serde_json::Value) -> serde_json::Result<Option<SearchPage>> {
+    let Some(jobs) = cached.get("jobs") else { return Ok(None) };
+    let jobs: Vec<Job> = serde_json::from_value(jobs.clone())?;
+    let next = cached.get("next").and_then(|n| n.as_str()).map(Cursor::decode);
+    Ok(Some(SearchPage { jobs, next }))
+}
+
+#[async_trait]
+impl JobSource for CachedSource<'_> {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn host(&self) -> &str {
+        self.inner.host()
+    }
+
+    async fn search(&self, req: &SearchRequest) -> anyhow::Result<SearchPage> {
+        let Some(ttl) = self.ttl else {
+            return self.search_inner(req).await;
+        };
+
+        let key = cache_key(self.inner.name(), req);
+        if !self.force_refresh {
+            if let Some(page) = self.lookup(&key).await {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(page);
+            }
+        }
+
+        let page = self.search_inner(req).await?;
+        let response = page_to_json(&page);
+        let expires_at = Utc::now() + ttl;
+        if let Err(e) = self
+            .db
+            .put_search_cache(&key, self.inner.name(), &req.params_json(), &response, page.jobs.len(), expires_at)
+            .await
+        {
+            eprintln!("put_search_cache error: {e}");
+        }
+        Ok(page)
+    }
+
+    fn enriches(&self) -> bool {
+        self.inner.enriches()
+    }
+
+    fn enrich_concurrency(&self) -> usize {
+        self.inner.enrich_concurrency()
+    }
+
+    async fn enrich(&self, job: Job) -> anyhow::Result<Job> {
+        self.inner.enrich(job).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(query: &str, cursor: Cursor) -> SearchRequest {
+        SearchRequest { query: query.into(), country: Some("us".into()), num_pages: 1, cursor, ..Default::default() }
+    }
+
+    #[test]
+    fn cache_key_depends_on_source_params_and_cursor() {
+        let key = cache_key("jsearch", &request("rust", Cursor::Start));
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, cache_key("jsearch", &request("rust", Cursor::Start)));
+        assert_ne!(key, cache_key("linkedin", &request("rust", Cursor::Start)));
+        assert_ne!(key, cache_key("jsearch", &request("golang", Cursor::Start)));
+        assert_ne!(key, cache_key("jsearch", &request("rust", Cursor::Page(2))));
+    }
+
+    #[test]
+    fn cached_page_round_trips() {
+        let job = Job {
+            job_id: "mockJS001==".into(),
+            job_title: Some("Senior Rust Engineer".into()),
+            ..Default::default()
+        };
+        let page = SearchPage { jobs: vec![job], next: Some(Cursor::Token("abc".into())) };
+        let stored = page_to_json(&page);
+        let back = page_from_json(&stored).unwrap().unwrap();
+        assert_eq!(back.jobs.len(), 1);
+        assert_eq!(back.jobs[0].job_id, "mockJS001==");
+        assert_eq!(back.jobs[0].job_title.as_deref(), Some("Senior Rust Engineer"));
+        assert_eq!(back.next, Some(Cursor::Token("abc".into())));
+
+        let last = page_from_json(&page_to_json(&SearchPage { jobs: vec![], next: None })).unwrap().unwrap();
+        assert!(last.jobs.is_empty() && last.next.is_none());
+        assert!(page_from_json(&json!({"next": null})).unwrap().is_none());
+        assert!(page_from_json(&json!({"jobs": "rusak"})).is_err());
+    }
+}
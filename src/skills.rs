@@ -0,0 +1,257 @@
+// src/skills.rs
+//
+// Ekstraksi skill/teknologi dari `job_description` + `job_highlights_json` saat job disimpan.
+// Daftar skill dibaca dari file taksonomi yang bisa diedit (`SKILLS_FILE`, default
+// `skills.json`): nama kanonik + alias, mis.
+//
+//   [ { "name": "Kubernetes", "aliases": ["k8s", "kube"] }, ... ]
+//
+// Pencocokan per kata (huruf kecil, tanda baca diabaikan kecuali `+ # .` di dalam kata,
+// supaya "C++", "C#", "Node.js" dan ".NET" tetap utuh); alias multi-kata ("machine learning")
+// dicocokkan sebagai urutan kata. Nama yang juga kata biasa bahasa Inggris ("Rust", "React")
+// diberi `"case_sensitive": true`: nama kanoniknya hanya cocok dengan kapitalisasi persis
+// (alias tetap case-insensitive). Hasil disimpan di tabel `job_skills` (nama kanonik).
+// Taksonomi berubah -> restart lalu `cargo run -- skills`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::database::Database;
+use crate::models::Job;
+
+#[derive(Debug, Deserialize)]
+struct SkillEntry {
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    /// nama kanonik hanya cocok dengan kapitalisasi persis ("Rust", bukan "rust")
+    #[serde(default)]
+    case_sensitive: bool,
+}
+
+/// Satu pola pencocokan: urutan kata huruf kecil, index `names`, dan (untuk nama
+/// case-sensitive) urutan kata dengan kapitalisasi asli yang juga harus sama.
+#[derive(Debug)]
+struct Pattern {
+    words: Vec<String>,
+    idx: usize,
+    cased: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+pub struct Taxonomy {
+    /// kata pertama alias (huruf kecil) -> pola yang diawali kata itu
+    patterns: HashMap<String, Vec<Pattern>>,
+    names: Vec<String>,
+}
+
+/// Pecah teks jadi kata huruf kecil; `+ # .` dipertahankan di dalam kata.
+fn tokenize(text: &str) -> Vec<String> {
+    tokenize_cased(text).into_iter().map(|w| w.to_lowercase()).collect()
+}
+
+/// Seperti `tokenize`, tapi kapitalisasi asli dipertahankan.
+fn tokenize_cased(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || matches!(c, '+' | '#' | '.')))
+        .map(|w| w.trim_end_matches('.').trim_start_matches(['#', '+']))
+        // titik di awal hanya untuk nama seperti ".net"
+        .map(|w| if w.starts_with("..") { w.trim_start_matches('.') } else { w })
+        .filter(|w| !w.is_empty() && *w != ".")
+        .map(str::to_string)
+        .collect()
+}
+
+impl Taxonomy {
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let entries: Vec<SkillEntry> =
+            serde_json::from_str(text).map_err(|e| format!("JSON taksonomi skill tidak valid: {e}"))?;
+
+        let mut tax = Taxonomy::default();
+        for entry in entries {
+            let name = entry.name.trim().to_string();
+            if name.is_empty() {
+                continue;
+            }
+            let idx = tax.names.len();
+            let name_cased = entry.case_sensitive.then(|| tokenize_cased(&name));
+            let aliases = entry.aliases.iter().map(|a| (a, None));
+            for (alias, cased) in std::iter::once((&name, name_cased)).chain(aliases) {
+                let words = tokenize(alias);
+                let Some(first) = words.first().cloned() else { continue };
+                let list = tax.patterns.entry(first).or_default();
+                if !list.iter().any(|p| p.words == words && p.idx == idx && p.cased == cased) {
+                    list.push(Pattern { words, idx, cased });
+                }
+            }
+            tax.names.push(name);
+        }
+        Ok(tax)
+    }
+
+    /// `SKILLS_FILE`; file tidak ada / rusak -> taksonomi kosong (tidak ada skill) + pesan di stderr.
+    pub fn from_env() -> Self {
+        let path = env::var("SKILLS_FILE").unwrap_or_else(|_| "skills.json".to_string());
+        let loaded = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Taxonomy::from_json(&text));
+        match loaded {
+            Ok(tax) => tax,
+            Err(e) => {
+                eprintln!("skills: {path}: {e}; ekstraksi skill nonaktif");
+                Taxonomy::default()
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Nama kanonik skill yang disebut di `text`, urut abjad tanpa duplikat.
+    pub fn extract(&self, text: &str) -> Vec<String> {
+        let cased = tokenize_cased(text);
+        let words: Vec<String> = cased.iter().map(|w| w.to_lowercase()).collect();
+        let mut found = BTreeSet::new();
+        for (pos, word) in words.iter().enumerate() {
+            let Some(candidates) = self.patterns.get(word) else { continue };
+            for p in candidates {
+                let exact = p.cased.as_ref().is_none_or(|c| cased[pos..].starts_with(c));
+                if exact && words[pos..].starts_with(&p.words) {
+                    found.insert(self.names[p.idx].clone());
+                }
+            }
+        }
+        found.into_iter().collect()
+    }
+}
+
+/// Taksonomi proses ini (dimuat sekali dari env saat pertama dipakai).
+pub fn global() -> &'static Taxonomy {
+    static TAXONOMY: OnceLock<Taxonomy> = OnceLock::new();
+    TAXONOMY.get_or_init(Taxonomy::from_env)
+}
+
+fn collect_strings(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => out.push(s.clone()),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect_strings(v, out)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| collect_strings(v, out)),
+        _ => {}
+    }
+}
+
+/// Teks yang dipindai: deskripsi + semua string di highlights (Qualifications, dst.).
+/// `None` jika deskripsi kosong (mis. hasil search tanpa deskripsi, sedangkan DB tetap
+/// menyimpan deskripsi lama); skill lama dipertahankan.
+fn job_text(job: &Job) -> Option<String> {
+    let desc = job.job_description.as_deref().filter(|d| !d.trim().is_empty())?;
+    let mut parts = vec![desc.to_string()];
+    if let Some(h) = &job.job_highlights_json {
+        collect_strings(h, &mut parts);
+    }
+    Some(parts.join("\n"))
+}
+
+/// Ekstrak + simpan skill satu job. Return jumlah skill, `None` jika job dilewati.
+pub async fn store(db: &Database, job: &Job) -> Result<Option<usize>, sqlx::Error> {
+    let tax = global();
+    if tax.is_empty() {
+        return Ok(None);
+    }
+    let Some(text) = job_text(job) else { return Ok(None) };
+    let skills = tax.extract(&text);
+    db.replace_job_skills(&job.job_id, &skills).await?;
+    Ok(Some(skills.len()))
+}
+
+/// `job_finder skills`: ekstrak ulang skill semua job (mis. setelah taksonomi diedit).
+/// Mengembalikan exit code.
+pub async fn run_cli(db: &Database) -> i32 {
+    if global().is_empty() {
+        eprintln!("skills: taksonomi kosong, tidak ada yang diekstrak");
+        return 1;
+    }
+    let job_ids = match db.job_ids_oldest_first().await {
+        Ok(ids) => ids,
+        Err(e) => {
+            eprintln!("skills error: {e}");
+            return 1;
+        }
+    };
+
+    let (mut checked, mut tagged) = (0, 0);
+    for job_id in job_ids {
+        let job = match db.find_job(&job_id).await {
+            Ok(Some(job)) => job,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("skills error: {e}");
+                return 1;
+            }
+        };
+        match store(db, &job).await {
+            Ok(Some(n)) => {
+                checked += 1;
+                if n > 0 {
+                    tagged += 1;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("skills error: {e}");
+                return 1;
+            }
+        }
+    }
+    println!("skills: {checked} job dipindai, {tagged} job punya skill");
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_keeps_symbol_names() {
+        assert_eq!(tokenize("C#, C++ and .NET."), ["c#", "c++", "and", ".net"]);
+        assert_eq!(tokenize("Node.js/React.js"), ["node.js", "react.js"]);
+        assert_eq!(tokenize("We use Go. Rust..."), ["we", "use", "go", "rust"]);
+        assert_eq!(tokenize("#hiring +kotlin ...NET"), ["hiring", "kotlin", "net"]);
+        assert_eq!(tokenize(" . ... "), Vec::<String>::new());
+    }
+
+    fn taxonomy() -> Taxonomy {
+        Taxonomy::from_json(
+            r#"[
+                { "name": "C#", "aliases": ["csharp"] },
+                { "name": "C++", "aliases": [] },
+                { "name": ".NET", "aliases": ["dotnet"] },
+                { "name": "Machine Learning", "aliases": ["ml"] },
+                { "name": "Rust", "aliases": ["rustlang"], "case_sensitive": true },
+                { "name": "React", "aliases": ["react.js"], "case_sensitive": true }
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn extract_matches_symbols_and_phrases() {
+        let tax = taxonomy();
+        assert_eq!(tax.extract("C#/.NET and c++ developer"), [".NET", "C#", "C++"]);
+        assert_eq!(tax.extract("applied machine   learning"), ["Machine Learning"]);
+        assert_eq!(tax.extract("machine shop"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn case_sensitive_names_skip_prose() {
+        let tax = taxonomy();
+        assert_eq!(tax.extract("Backend in Rust, frontend in React"), ["React", "Rust"]);
+        assert_eq!(tax.extract("remove rust and react quickly"), Vec::<String>::new());
+        assert_eq!(tax.extract("RUST"), Vec::<String>::new());
+        // alias tetap case-insensitive
+        assert_eq!(tax.extract("RustLang + REACT.JS"), ["React", "Rust"]);
+    }
+}
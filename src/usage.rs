@@ -0,0 +1,153 @@
+// src/usage.rs
+//
+// Pencatatan setiap panggilan keluar ke RapidAPI (tabel api_calls) + soft limit
+// harian per host. Client cukup memanggil `record` (non-blocking); penulisan ke DB
+// dilakukan task background supaya latency request tidak bertambah.
+
+use std::env;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use tokio::sync::mpsc;
+
+use crate::database::Database;
+use crate::models::ApiCall;
+
+#[derive(Clone)]
+pub struct UsageTracker {
+    tx: mpsc::UnboundedSender<ApiCall>,
+    db: Database,
+    soft_limit_daily: Option<i64>,
+}
+
+impl UsageTracker {
+    /// Jalankan writer background. `RAPIDAPI_SOFT_LIMIT_DAILY` (opsional, per host)
+    /// membatasi jumlah call per hari; kosong / 0 = tanpa batas.
+    pub fn spawn(db: Database) -> Self {
+        let soft_limit_daily = env::var("RAPIDAPI_SOFT_LIMIT_DAILY")
+            .ok()
+            .and_then(|s| s.trim().parse::<i64>().ok())
+            .filter(|n| *n > 0);
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<ApiCall>();
+        let writer_db = db.clone();
+        tokio::spawn(async move {
+            while let Some(call) = rx.recv().await {
+                if let Err(e) = writer_db.insert_api_call(&call).await {
+                    eprintln!("insert_api_call({} {}) error: {e}", call.host, call.endpoint);
+                }
+            }
+        });
+
+        Self { tx, db, soft_limit_daily }
+    }
+
+    pub fn soft_limit_daily(&self) -> Option<i64> {
+        self.soft_limit_daily
+    }
+
+    pub fn record(&self, call: ApiCall) {
+        // receiver hanya hilang saat shutdown; abaikan
+        let _ = self.tx.send(call);
+    }
+
+    /// `Err(pesan)` jika call hari ini ke `host` sudah mencapai soft limit.
+    pub async fn check_soft_limit(&self, host: &str) -> Result<(), String> {
+        let Some(limit) = self.soft_limit_daily else { return Ok(()) };
+        let used = self.db.count_api_calls_today(host).await.map_err(|e| format!("DB error: {e}"))?;
+        if used >= limit {
+            return Err(format!(
+                "Soft limit harian {host} tercapai ({used}/{limit} call). Fetch baru ditolak; lihat /usage."
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Error search ketika soft limit harian tercapai; dibungkus `anyhow` oleh `CachedSource`
+/// dan dikenali lagi oleh `job_source::fetch_and_store` (-> `FetchError::SoftLimit`).
+#[derive(Debug)]
+pub struct SoftLimitExceeded(pub String);
+
+impl std::fmt::Display for SoftLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SoftLimitExceeded {}
+
+/// Bangun record `ApiCall` dari hasil satu request. `host` adalah host logis client
+/// (mis. `jsearch.p.rapidapi.com`), bukan host URL yang bisa saja mock / base URL lain.
+pub fn api_call_from(
+    host: &str,
+    url: &reqwest::Url,
+    status: Option<u16>,
+    headers: Option<&HeaderMap>,
+    latency: Duration,
+    error: Option<String>,
+) -> ApiCall {
+    let mut ratelimit = serde_json::Map::new();
+    let mut min_remaining: Option<i64> = None;
+    let mut requests_remaining: Option<i64> = None;
+
+    for (name, value) in headers.into_iter().flat_map(|h| h.iter()) {
+        let name = name.as_str();
+        if !name.starts_with("x-ratelimit-") {
+            continue;
+        }
+        let Ok(v) = value.to_str() else { continue };
+        ratelimit.insert(name.to_string(), serde_json::Value::String(v.to_string()));
+
+        if name.ends_with("-remaining") {
+            if let Ok(n) = v.trim().parse::<i64>() {
+                min_remaining = Some(min_remaining.map_or(n, |m| m.min(n)));
+                if name == "x-ratelimit-requests-remaining" {
+                    requests_remaining = Some(n);
+                }
+            }
+        }
+    }
+
+    ApiCall {
+        host: host.to_string(),
+        endpoint: url.path().to_string(),
+        status_code: status,
+        latency_ms: latency.as_millis() as i64,
+        requests_remaining: requests_remaining.or(min_remaining),
+        ratelimit_json: (!ratelimit.is_empty()).then_some(serde_json::Value::Object(ratelimit)),
+        error: error.map(|e| e.chars().take(500).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn api_call_uses_logical_host_not_url_host() {
+        let url = reqwest::Url::parse("http://127.0.0.1:8089/mock/search?query=rust").unwrap();
+        let call = api_call_from("jsearch.p.rapidapi.com", &url, Some(200), None, Duration::from_millis(12), None);
+        assert_eq!(call.host, "jsearch.p.rapidapi.com");
+        assert_eq!(call.endpoint, "/mock/search");
+        assert_eq!(call.latency_ms, 12);
+    }
+
+    #[test]
+    fn api_call_reads_ratelimit_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-requests-remaining", HeaderValue::from_static("42"));
+        headers.insert("x-ratelimit-rapid-free-plans-hard-limit-remaining", HeaderValue::from_static("7"));
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+        let url = reqwest::Url::parse("https://jsearch.p.rapidapi.com/search").unwrap();
+        let call = api_call_from("jsearch.p.rapidapi.com", &url, Some(200), Some(&headers), Duration::ZERO, None);
+        assert_eq!(call.requests_remaining, Some(42));
+        let json = call.ratelimit_json.unwrap();
+        assert_eq!(json.as_object().unwrap().len(), 2);
+
+        headers.remove("x-ratelimit-requests-remaining");
+        let call = api_call_from("jsearch.p.rapidapi.com", &url, Some(200), Some(&headers), Duration::ZERO, None);
+        assert_eq!(call.requests_remaining, Some(7));
+    }
+}
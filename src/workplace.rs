@@ -0,0 +1,459 @@
+// src/workplace.rs
+//
+// Klasifikasi tempat kerja setiap job: `workplace_type` (remote / hybrid / onsite) dan, untuk
+// job remote, `remote_region_restriction` ("Worldwide", "US", "Europe, UK", ...).
+// `job_is_remote` hanya diisi sebagian sumber dan tidak membedakan "remote (US only)" dari
+// "remote worldwide", jadi sinyal digabung dengan urutan prioritas:
+//   1. flag terstruktur provider di raw_json (LinkedIn / Lever / Ashby `workplaceType`)
+//   2. judul + lokasi ("Remote - US", "Hybrid", "On-site")
+//   3. `job_is_remote`
+//   4. frasa di deskripsi ("fully remote", "3 days in the office", "on-site role", ...)
+// Wilayah remote dicari di lokasi, judul dan deskripsi ("US only", "based in Europe",
+// "Remote (Canada)", "work from anywhere"). Wilayah spesifik menang atas "worldwide";
+// tidak ada petunjuk -> NULL (tidak diketahui). Hasil hanya disimpan di `workplace_type` /
+// `remote_region_restriction`; `job_is_remote` tetap nilai dari sumber.
+
+use serde_json::Value;
+
+use crate::database::Database;
+use crate::models::Job;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkplaceType {
+    Remote,
+    Hybrid,
+    Onsite,
+}
+
+impl WorkplaceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkplaceType::Remote => "remote",
+            WorkplaceType::Hybrid => "hybrid",
+            WorkplaceType::Onsite => "onsite",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WorkplaceType::Remote => "Remote",
+            WorkplaceType::Hybrid => "Hybrid",
+            WorkplaceType::Onsite => "On-site",
+        }
+    }
+
+    pub fn from_param(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "remote" => Some(WorkplaceType::Remote),
+            "hybrid" => Some(WorkplaceType::Hybrid),
+            "onsite" => Some(WorkplaceType::Onsite),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Workplace {
+    pub workplace_type: Option<WorkplaceType>,
+    pub remote_region_restriction: Option<String>,
+}
+
+const WORLDWIDE: &str = "Worldwide";
+
+/// Label tampilan dari kolom tersimpan, mis. "Remote (US)", "Hybrid", "On-site".
+pub fn display_label(job: &Job) -> Option<String> {
+    let wt = WorkplaceType::from_param(job.workplace_type.as_deref()?)?;
+    match (wt, job.remote_region_restriction.as_deref()) {
+        (WorkplaceType::Remote, Some(region)) => Some(format!("Remote ({region})")),
+        _ => Some(wt.label().to_string()),
+    }
+}
+
+/// Label wilayah + alias (huruf kecil, sudah dinormalisasi seperti `normalize`).
+const REGIONS: &[(&str, &[&str])] = &[
+    ("US", &["us", "usa", "united states", "united states of america"]),
+    ("Canada", &["canada"]),
+    ("North America", &["north america"]),
+    ("LATAM", &["latam", "latin america", "south america"]),
+    ("UK", &["uk", "united kingdom", "great britain"]),
+    ("Europe", &["europe", "eu", "european union"]),
+    ("EMEA", &["emea"]),
+    ("APAC", &["apac", "asia pacific", "asia"]),
+    ("Indonesia", &["indonesia"]),
+    ("India", &["india"]),
+    ("Philippines", &["philippines"]),
+    ("Singapore", &["singapore"]),
+    ("Australia", &["australia"]),
+    ("Germany", &["germany"]),
+    ("Netherlands", &["netherlands"]),
+    ("Spain", &["spain"]),
+    ("Brazil", &["brazil"]),
+    ("Mexico", &["mexico"]),
+];
+
+/// Alias yang juga kata biasa ("join us remotely", "within us"): di deskripsi hanya cocok
+/// jika ditulis huruf besar ("US only", "remote US") atau lewat pola "the {}" ("based in
+/// the us"); di judul/lokasi huruf apa pun.
+const AMBIGUOUS_ALIASES: &[&str] = &["us"];
+
+/// Pola wilayah; `{}` diganti alias.
+const REGION_PATTERNS: &[&str] = &[
+    "{} only",
+    "remote {}",
+    "{} remote",
+    "based in {}",
+    "based in the {}",
+    "located in {}",
+    "located in the {}",
+    "reside in {}",
+    "reside in the {}",
+    "residing in {}",
+    "residing in the {}",
+    "residents of {}",
+    "residents of the {}",
+    "anywhere in {}",
+    "anywhere in the {}",
+    "authorized to work in {}",
+    "authorized to work in the {}",
+    "within {}",
+    "within the {}",
+];
+
+const WORLDWIDE_PHRASES: &[&str] = &[
+    "worldwide",
+    "anywhere in the world",
+    "work from anywhere",
+    "remote anywhere",
+    "anywhere remote",
+    "globally remote",
+    "remote global",
+    "location independent",
+];
+
+/// Bukan "hybrid" saja: "hybrid cloud" / "hybrid app" sering muncul di posting non-hybrid.
+const HYBRID_PHRASES: &[&str] = &[
+    "hybrid role",
+    "hybrid position",
+    "hybrid work",
+    "hybrid working",
+    "hybrid schedule",
+    "hybrid model",
+    "hybrid arrangement",
+    "hybrid remote",
+    "remote hybrid",
+    "days in the office",
+    "days per week in the office",
+    "days a week in the office",
+    "days per week on site",
+    "days a week on site",
+    "partially remote",
+];
+
+const REMOTE_PHRASES: &[&str] = &[
+    "fully remote",
+    "100 remote",
+    "remote first",
+    "remote position",
+    "remote role",
+    "this is a remote",
+    "work from home",
+    "work remotely",
+    "wfh",
+    "remote friendly",
+    "kerja remote",
+    "kerja dari rumah",
+];
+
+const ONSITE_PHRASES: &[&str] = &[
+    "on site",
+    "onsite",
+    "in office",
+    "office based",
+    "not a remote",
+    "no remote",
+    "wfo",
+    "kerja di kantor",
+];
+
+/// Singkatan bertitik -> kata ("U.S." -> "usa"), tanpa peduli huruf besar/kecil.
+fn expand_abbreviations(s: &str) -> String {
+    let mut out = s.to_string();
+    for (from, to) in [("u.s.a.", "usa"), ("u.s.", "usa"), ("u.k.", "uk")] {
+        // to_ascii_lowercase tidak mengubah panjang byte, jadi index tetap cocok
+        while let Some(i) = out.to_ascii_lowercase().find(from) {
+            out.replace_range(i..i + from.len(), to);
+        }
+    }
+    out
+}
+
+/// Huruf kecil, "u.s." -> "usa", selain huruf/angka jadi spasi, spasi dirapatkan, diapit spasi
+/// supaya `contains(" frasa ")` cocok per kata.
+fn normalize(s: &str) -> String {
+    normalize_keeping(s, &[])
+}
+
+/// Seperti `normalize`, tapi kata yang ditulis persis huruf besar salah satu `keep`
+/// ("US") tetap huruf besar.
+fn normalize_keeping(s: &str, keep: &[&str]) -> String {
+    let expanded = expand_abbreviations(s);
+    let words: Vec<String> = expanded
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let kept = keep.iter().any(|k| k.to_uppercase() == w);
+            if kept { w.to_string() } else { w.to_lowercase() }
+        })
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+fn has_phrase(text: &str, phrases: &[&str]) -> bool {
+    phrases.iter().any(|p| text.contains(&format!(" {p} ")))
+}
+
+/// Nilai `workplaceType` / `workplaceTypes` (string atau array) / `workRemoteAllowed` di raw_json.
+fn provider_workplace(raw: Option<&Value>) -> Option<WorkplaceType> {
+    let obj = raw?.as_object()?;
+    for key in ["workplaceType", "workplaceTypes"] {
+        let value = match obj.get(key) {
+            Some(Value::String(s)) => Some(s.as_str()),
+            Some(Value::Array(items)) => items.iter().find_map(|v| v.as_str()),
+            _ => None,
+        };
+        if let Some(wt) = value.and_then(WorkplaceType::from_param) {
+            return Some(wt);
+        }
+    }
+    match obj.get("workRemoteAllowed").and_then(|v| v.as_bool()) {
+        Some(true) => Some(WorkplaceType::Remote),
+        _ => None,
+    }
+}
+
+fn text_workplace(text: &str) -> Option<WorkplaceType> {
+    if has_phrase(text, &["hybrid"]) {
+        Some(WorkplaceType::Hybrid)
+    } else if has_phrase(text, &["remote", "anywhere", "work from home", "wfh"]) {
+        Some(WorkplaceType::Remote)
+    } else if has_phrase(text, &["on site", "onsite", "in office"]) {
+        Some(WorkplaceType::Onsite)
+    } else {
+        None
+    }
+}
+
+fn description_workplace(text: &str) -> Option<WorkplaceType> {
+    if has_phrase(text, HYBRID_PHRASES) {
+        Some(WorkplaceType::Hybrid)
+    } else if has_phrase(text, REMOTE_PHRASES) {
+        Some(WorkplaceType::Remote)
+    } else if has_phrase(text, ONSITE_PHRASES) {
+        Some(WorkplaceType::Onsite)
+    } else {
+        None
+    }
+}
+
+/// Wilayah yang disebut di `text` dengan salah satu `REGION_PATTERNS`, urut sesuai `REGIONS`.
+/// `prose`: teks bebas dari `normalize_keeping(.., AMBIGUOUS_ALIASES)`; alias ambigu dicari
+/// dalam huruf besar, atau huruf kecil hanya dengan pola "the {}".
+fn regions_in(text: &str, prose: bool) -> Vec<&'static str> {
+    let found = |p: &str, alias: &str| text.contains(&format!(" {} ", p.replace("{}", alias)));
+    REGIONS
+        .iter()
+        .filter(|(_, aliases)| {
+            aliases.iter().any(|alias| {
+                if prose && AMBIGUOUS_ALIASES.contains(alias) {
+                    let upper = alias.to_uppercase();
+                    REGION_PATTERNS
+                        .iter()
+                        .any(|p| found(p, &upper) || (p.contains("the {}") && found(p, alias)))
+                } else {
+                    REGION_PATTERNS.iter().any(|p| found(p, alias))
+                }
+            })
+        })
+        .map(|(label, _)| *label)
+        .collect()
+}
+
+/// Lokasi remote yang hanya berisi nama wilayah ("United States", "Europe") -> wilayah itu.
+fn location_region(location: &str) -> Option<&'static str> {
+    let loc = normalize(location);
+    let loc = loc.trim();
+    REGIONS
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&loc))
+        .map(|(label, _)| *label)
+}
+
+fn region_restriction(job: &Job, head: &str, description: &str) -> Option<String> {
+    let mut regions = regions_in(head, false);
+    if regions.is_empty() {
+        let prose = normalize_keeping(job.job_description.as_deref().unwrap_or(""), AMBIGUOUS_ALIASES);
+        regions = regions_in(&prose, true);
+    }
+    if regions.is_empty() {
+        if let Some(r) = job.job_location.as_deref().and_then(location_region) {
+            regions.push(r);
+        }
+    }
+    if !regions.is_empty() {
+        return Some(regions.join(", "));
+    }
+    let location = normalize(job.job_location.as_deref().unwrap_or(""));
+    if has_phrase(head, WORLDWIDE_PHRASES)
+        || has_phrase(description, WORLDWIDE_PHRASES)
+        || location.trim() == "anywhere"
+    {
+        return Some(WORLDWIDE.to_string());
+    }
+    None
+}
+
+pub fn classify(job: &Job) -> Workplace {
+    let head = normalize(&format!(
+        "{} | {}",
+        job.job_title.as_deref().unwrap_or(""),
+        job.job_location.as_deref().unwrap_or("")
+    ));
+    let description = normalize(job.job_description.as_deref().unwrap_or(""));
+
+    let workplace_type = provider_workplace(job.raw_json.as_ref())
+        .or_else(|| text_workplace(&head))
+        .or(match job.job_is_remote {
+            Some(true) => Some(WorkplaceType::Remote),
+            _ => None,
+        })
+        .or_else(|| description_workplace(&description))
+        .or(match job.job_is_remote {
+            Some(false) => Some(WorkplaceType::Onsite),
+            _ => None,
+        });
+
+    let remote_region_restriction = match workplace_type {
+        Some(WorkplaceType::Remote) => region_restriction(job, &head, &description),
+        _ => None,
+    };
+    Workplace { workplace_type, remote_region_restriction }
+}
+
+/// Klasifikasi + simpan satu job. Tanpa deskripsi (mis. hasil search ringkas) hasil kosong
+/// tidak menimpa klasifikasi lama.
+pub async fn store(db: &Database, job: &Job) -> Result<Workplace, sqlx::Error> {
+    let wp = classify(job);
+    let has_description = job.job_description.as_deref().map(|d| !d.trim().is_empty()).unwrap_or(false);
+    db.set_job_workplace(&job.job_id, &wp, !has_description).await?;
+    Ok(wp)
+}
+
+/// `job_finder workplace`: klasifikasi ulang semua job (mis. setelah aturan diubah).
+/// Mengembalikan exit code.
+pub async fn run_cli(db: &Database) -> i32 {
+    let job_ids = match db.job_ids_oldest_first().await {
+        Ok(ids) => ids,
+        Err(e) => {
+            eprintln!("workplace error: {e}");
+            return 1;
+        }
+    };
+
+    let (mut remote, mut hybrid, mut onsite, mut unknown) = (0, 0, 0, 0);
+    for job_id in &job_ids {
+        let job = match db.find_job(job_id).await {
+            Ok(Some(job)) => job,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("workplace error: {e}");
+                return 1;
+            }
+        };
+        let wp = classify(&job);
+        if let Err(e) = db.set_job_workplace(job_id, &wp, false).await {
+            eprintln!("workplace error: {e}");
+            return 1;
+        }
+        match wp.workplace_type {
+            Some(WorkplaceType::Remote) => remote += 1,
+            Some(WorkplaceType::Hybrid) => hybrid += 1,
+            Some(WorkplaceType::Onsite) => onsite += 1,
+            None => unknown += 1,
+        }
+    }
+    println!(
+        "workplace: {} job, {remote} remote, {hybrid} hybrid, {onsite} onsite, {unknown} tidak diketahui",
+        job_ids.len()
+    );
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(title: &str, location: &str, description: &str, is_remote: Option<bool>) -> Job {
+        Job {
+            job_title: Some(title.into()),
+            job_location: Some(location.into()),
+            job_description: Some(description.into()),
+            job_is_remote: is_remote,
+            ..Default::default()
+        }
+    }
+
+    /// (judul, lokasi, deskripsi, job_is_remote, tipe, wilayah)
+    type Case = (&'static str, &'static str, &'static str, Option<bool>, Option<WorkplaceType>, Option<&'static str>);
+
+    #[test]
+    fn classify_table() {
+        use WorkplaceType::*;
+        let cases: &[Case] = &[
+            ("Backend Engineer", "Remote - US", "", None, Some(Remote), Some("US")),
+            ("Backend Engineer (Remote, Canada)", "", "", None, Some(Remote), Some("Canada")),
+            ("Engineer", "United States", "", Some(true), Some(Remote), Some("US")),
+            ("Engineer", "Anywhere", "", Some(true), Some(Remote), Some("Worldwide")),
+            ("Engineer", "", "We are fully remote, work from anywhere.", None, Some(Remote), Some("Worldwide")),
+            ("Engineer", "", "Fully remote. Must be based in the US.", None, Some(Remote), Some("US")),
+            ("Engineer", "", "Fully remote, U.S. only.", None, Some(Remote), Some("US")),
+            ("Engineer", "", "Fully remote (US or Canada only).", None, Some(Remote), Some("US, Canada")),
+            ("Engineer", "", "Fully remote, within the us.", None, Some(Remote), Some("US")),
+            ("Engineer", "", "Remote role for US residents: US only.", None, Some(Remote), Some("US")),
+            ("Engineer", "", "Remote role, residents of the EU or UK only.", None, Some(Remote), Some("UK, Europe")),
+            ("Engineer", "", "Join us remote first! Reply within us soon.", None, Some(Remote), None),
+            ("Engineer", "", "Work remotely with us remote folks; tell us only once.", None, Some(Remote), None),
+            ("Engineer", "Jakarta", "Hybrid work: 3 days in the office.", None, Some(Hybrid), None),
+            ("Engineer (Hybrid)", "Remote", "", Some(true), Some(Hybrid), None),
+            ("Engineer", "Jakarta", "We build hybrid cloud apps. On-site role.", None, Some(Onsite), None),
+            ("Engineer", "Jakarta", "Nothing about place here.", Some(false), Some(Onsite), None),
+            ("Engineer", "Jakarta", "Nothing about place here.", None, None, None),
+        ];
+        for (title, location, description, is_remote, wt, region) in cases {
+            let wp = classify(&job(title, location, description, *is_remote));
+            assert_eq!(wp.workplace_type, *wt, "{title} | {location} | {description}");
+            assert_eq!(wp.remote_region_restriction.as_deref(), *region, "{title} | {location} | {description}");
+        }
+    }
+
+    #[test]
+    fn provider_flag_wins() {
+        let mut j = job("Engineer (Remote)", "", "", Some(true));
+        j.raw_json = Some(serde_json::json!({ "workplaceType": "hybrid" }));
+        assert_eq!(classify(&j).workplace_type, Some(WorkplaceType::Hybrid));
+        j.raw_json = Some(serde_json::json!({ "workplaceTypes": ["on-site"] }));
+        assert_eq!(classify(&j).workplace_type, Some(WorkplaceType::Onsite));
+    }
+
+    #[test]
+    fn from_param_table() {
+        for (input, expected) in [
+            ("remote", Some(WorkplaceType::Remote)),
+            ("On-site", Some(WorkplaceType::Onsite)),
+            ("on_site", Some(WorkplaceType::Onsite)),
+            (" HYBRID ", Some(WorkplaceType::Hybrid)),
+            ("office", None),
+        ] {
+            assert_eq!(WorkplaceType::from_param(input), expected, "{input}");
+        }
+    }
+}